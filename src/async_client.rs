@@ -0,0 +1,128 @@
+use crate::{
+    client::{is_retryable, map_connect_error},
+    protocol::{describe_framed, encode_args, parse_response, split_message},
+    Command, YabaiClient, YabaiError,
+};
+#[cfg(feature = "serde")]
+use crate::{DisplayInfo, SpaceInfo, Windows};
+#[cfg(feature = "serde")]
+use serde::de::DeserializeOwned;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::UnixStream,
+};
+
+/// Async versions of the client methods, using `tokio`.
+///
+/// These mirror their blocking counterparts, and behave identically apart from not blocking
+/// the executor while waiting on the socket.
+impl YabaiClient {
+    /// Send a command to yabai as a string of space-separated arguments.
+    pub async fn send_async(&self, message: &str) -> Result<Option<String>, YabaiError> {
+        self.send_args_async(&split_message(message)).await
+    }
+
+    /// Send a command to yabai as a list of arguments.
+    pub async fn send_args_async<S: AsRef<str>>(
+        &self,
+        args: &[S],
+    ) -> Result<Option<String>, YabaiError> {
+        let args = args.iter().map(AsRef::as_ref).collect::<Vec<&str>>();
+
+        self.send_raw_async(encode_args(&args).as_bytes(), &args.join(" "))
+            .await
+    }
+
+    /// Send an already framed message to yabai, prefixed with its length.
+    pub async fn send_framed_async(&self, bytes: &[u8]) -> Result<Option<String>, YabaiError> {
+        self.send_raw_async(bytes, &describe_framed(bytes)).await
+    }
+
+    async fn send_raw_async(
+        &self,
+        message: &[u8],
+        command: &str,
+    ) -> Result<Option<String>, YabaiError> {
+        if self.record(command.to_string()) {
+            return Ok(None);
+        }
+
+        let exchange = async {
+            let mut buffer = Vec::new();
+            let mut stream = self.connect_async().await?;
+
+            #[cfg(feature = "log")]
+            log::debug!("sending message \"{}\"", message.escape_ascii());
+
+            stream.write_u32_le(message.len() as u32).await?;
+            stream.write_all(message).await?;
+
+            stream.read_to_end(&mut buffer).await?;
+
+            parse_response(command, buffer)
+        };
+
+        match self.timeout() {
+            Some(timeout) => tokio::time::timeout(timeout, exchange)
+                .await
+                .map_err(|_| YabaiError::Timeout)?,
+            None => exchange.await,
+        }
+    }
+
+    async fn connect_async(&self) -> Result<UnixStream, YabaiError> {
+        let path = self.socket_path()?;
+        let (retries, delay) = self.retry();
+        let mut attempt = 0;
+
+        loop {
+            match UnixStream::connect(&path).await {
+                Ok(stream) => return Ok(stream),
+                Err(error) if attempt < retries && is_retryable(&error) => {
+                    attempt += 1;
+                    tokio::time::sleep(delay).await;
+                }
+                Err(error) => return Err(map_connect_error(error, path)),
+            }
+        }
+    }
+
+    /// Send a `yabai::Command` to yabai.
+    pub async fn send_command_async(
+        &self,
+        command: &Command,
+    ) -> Result<Option<String>, YabaiError> {
+        command.validate()?;
+
+        self.send_args_async(&command.to_args()).await
+    }
+}
+
+/// Async versions of the query methods, which require the `serde` feature.
+#[cfg(feature = "serde")]
+impl YabaiClient {
+    /// Sends a query to yabai and deserializes the JSON response.
+    pub async fn query_async<T: DeserializeOwned>(&self, message: &str) -> Result<T, YabaiError> {
+        let result = self.send_async(message).await?;
+
+        match result {
+            Some(str) => Ok(serde_json::from_str::<T>(&str)?),
+            None => Err(YabaiError::EmptyResponse),
+        }
+    }
+
+    /// Queries yabai for information about all spaces.
+    pub async fn query_spaces_async(&self) -> Result<Vec<SpaceInfo>, YabaiError> {
+        self.query_async("query --spaces").await
+    }
+
+    /// Queries yabai for information about all displays.
+    pub async fn query_displays_async(&self) -> Result<Vec<DisplayInfo>, YabaiError> {
+        self.query_async("query --displays").await
+    }
+
+    /// Queries yabai for information about all windows.
+    pub async fn query_windows_async(&self) -> Result<Windows, YabaiError> {
+        self.query_async("query --windows").await
+    }
+}
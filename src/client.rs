@@ -0,0 +1,788 @@
+use crate::{
+    config::on_off,
+    protocol::{describe_framed, encode_args, read_command_response, split_message, write_framed},
+    Color, Command, ConfigKey, FocusFollowsMouse, FocusSpaceOption, SpaceIndex, SpaceSelector,
+    WindowId, WindowSelector, WindowToggle, YabaiError, YabaiErrorKind,
+};
+use std::{
+    io,
+    os::unix::net::UnixStream,
+    path::{Path, PathBuf},
+    sync::{mpsc, Arc, Mutex},
+    time::Duration,
+};
+
+/// A client for communicating with yabai over its socket.
+///
+/// The free functions in this crate use a default client, which resolves the socket path
+/// with `yabai::socket_path`. A client can instead target a specific socket, such as a mock
+/// socket in tests.
+///
+/// Example:
+///
+/// ```no_run
+/// # #[cfg(feature = "serde")]
+/// # {
+/// let client = yabai::YabaiClient::with_socket_path("/tmp/yabai_custom.socket");
+/// let windows = client.query_windows()?;
+/// # }
+/// # Ok::<(), yabai::YabaiError>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct YabaiClient {
+    socket_path: Option<PathBuf>,
+    timeout: Option<Duration>,
+    retries: u32,
+    retry_delay: Duration,
+    recorder: Option<Recorder>,
+}
+
+/// The messages recorded by a dry run client, shared between its clones.
+#[derive(Debug, Clone, Default)]
+struct Recorder(Arc<Mutex<Vec<String>>>);
+
+impl PartialEq for Recorder {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for Recorder {}
+
+impl YabaiClient {
+    /// Creates a client using the default socket path.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a client using the socket at the given path.
+    pub fn with_socket_path(path: impl Into<PathBuf>) -> Self {
+        Self {
+            socket_path: Some(path.into()),
+            ..Self::default()
+        }
+    }
+
+    /// Creates a client that records messages instead of sending them to yabai.
+    ///
+    /// Every message is recorded as its space-separated arguments, and `Ok(None)` is returned
+    /// without connecting to the socket. Queries fail with `YabaiError::EmptyResponse`, as no
+    /// response is received. Clones of the client share its recorded messages.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use yabai::{Command, WindowSelector};
+    ///
+    /// let client = yabai::YabaiClient::dry_run();
+    /// client.send("space --balance")?;
+    /// client.send_command(&Command::FocusWindow { window: WindowSelector::Next {} })?;
+    ///
+    /// assert_eq!(
+    ///     client.recorded_messages(),
+    ///     ["space --balance", "window --focus next"],
+    /// );
+    /// # Ok::<(), yabai::YabaiError>(())
+    /// ```
+    pub fn dry_run() -> Self {
+        Self {
+            recorder: Some(Recorder::default()),
+            ..Self::default()
+        }
+    }
+
+    /// Returns the messages recorded by a dry run client, in the order they were sent.
+    ///
+    /// Returns an empty list for clients that aren't a dry run.
+    pub fn recorded_messages(&self) -> Vec<String> {
+        match &self.recorder {
+            Some(recorder) => recorder.0.lock().unwrap_or_else(|e| e.into_inner()).clone(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Records the message if this is a dry run client, returning whether it was recorded.
+    pub(crate) fn record(&self, message: String) -> bool {
+        match &self.recorder {
+            Some(recorder) => {
+                recorder
+                    .0
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .push(message);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Sets a timeout for connecting to yabai, writing messages to it and reading its responses.
+    ///
+    /// When the timeout elapses, a `YabaiError::Timeout` is returned. By default, the client
+    /// blocks until yabai responds.
+    ///
+    /// Each of the three steps is bounded by the timeout separately, while the async client
+    /// bounds the whole exchange. The standard library can't bound connecting to a Unix socket,
+    /// so a connection that doesn't complete in time is left to finish on its own thread.
+    ///
+    /// A zero duration, which the standard library treats as invalid, is rejected:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// let client = yabai::YabaiClient::new().with_timeout(Duration::from_millis(500))?;
+    /// assert_eq!(client.timeout(), Some(Duration::from_millis(500)));
+    ///
+    /// assert!(matches!(
+    ///     yabai::YabaiClient::new().with_timeout(Duration::ZERO),
+    ///     Err(yabai::YabaiError::InvalidArgument(_))
+    /// ));
+    /// # Ok::<(), yabai::YabaiError>(())
+    /// ```
+    pub fn with_timeout(mut self, timeout: Duration) -> Result<Self, YabaiError> {
+        if timeout.is_zero() {
+            return Err(YabaiError::InvalidArgument(
+                "timeout must be greater than zero".to_string(),
+            ));
+        }
+
+        self.timeout = Some(timeout);
+        Ok(self)
+    }
+
+    /// Returns the timeout set with `with_timeout`, if any.
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Retries connecting to yabai up to `retries` times, waiting `delay` between attempts.
+    ///
+    /// Only failures to connect because the socket is missing or refuses the connection are
+    /// retried, such as while yabai restarts. Errors reported by yabai for a command are
+    /// returned without retrying, as the command was already received. By default, the client
+    /// doesn't retry.
+    ///
+    /// Example:
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    ///
+    /// let client = yabai::YabaiClient::new().with_retry(5, Duration::from_millis(200));
+    /// client.send("space --balance")?;
+    /// # Ok::<(), yabai::YabaiError>(())
+    /// ```
+    pub fn with_retry(mut self, retries: u32, delay: Duration) -> Self {
+        self.retries = retries;
+        self.retry_delay = delay;
+        self
+    }
+
+    /// Returns the number of times connecting is retried, and the delay between attempts.
+    pub fn retry(&self) -> (u32, Duration) {
+        (self.retries, self.retry_delay)
+    }
+
+    /// Returns the path of the socket this client connects to.
+    pub fn socket_path(&self) -> Result<PathBuf, YabaiError> {
+        match &self.socket_path {
+            Some(path) => Ok(path.clone()),
+            None => crate::socket_path(),
+        }
+    }
+
+    /// Returns whether yabai is accepting connections on this client's socket.
+    ///
+    /// Only connects to the socket, without sending anything, so it returns immediately. A
+    /// missing socket path, such as when `USER` is unset, returns `false`:
+    ///
+    /// ```
+    /// let client = yabai::YabaiClient::with_socket_path("/nonexistent/yabai.socket");
+    /// assert!(!client.is_yabai_running());
+    /// ```
+    pub fn is_yabai_running(&self) -> bool {
+        self.socket_path()
+            .map(|path| UnixStream::connect(path).is_ok())
+            .unwrap_or(false)
+    }
+
+    /// Send a command to yabai as a string of space-separated arguments.
+    ///
+    /// See the [yabai documentation](https://github.com/koekeishiya/yabai/wiki/Commands#message-passing-interface) for more information.
+    ///
+    /// The trailing newline yabai ends its responses with is removed:
+    ///
+    /// ```no_run
+    /// let client = yabai::YabaiClient::new();
+    /// let gap = client.send("config window_gap")?;
+    /// assert_eq!(gap.as_deref(), Some("2"));
+    /// # Ok::<(), yabai::YabaiError>(())
+    /// ```
+    pub fn send(&self, message: &str) -> Result<Option<String>, YabaiError> {
+        self.send_args(&split_message(message))
+    }
+
+    /// Send a command to yabai as a list of arguments.
+    ///
+    /// Unlike `send`, arguments are passed through as-is, so they may contain spaces.
+    ///
+    /// Invalid UTF-8 in the response is replaced with `U+FFFD`.
+    ///
+    /// ```no_run
+    /// let client = yabai::YabaiClient::new();
+    /// client.send_args(&["space", "--label", "team comms"])?;
+    /// # Ok::<(), yabai::YabaiError>(())
+    /// ```
+    pub fn send_args<S: AsRef<str>>(&self, args: &[S]) -> Result<Option<String>, YabaiError> {
+        let args = args.iter().map(AsRef::as_ref).collect::<Vec<&str>>();
+
+        self.send_raw(encode_args(&args).as_bytes(), &args.join(" "))
+    }
+
+    /// Send an already framed message to yabai, prefixed with its length.
+    ///
+    /// The bytes are sent exactly as given, so they must already contain the NUL byte ending
+    /// each argument and the final NUL byte ending the message, as `send_args` adds. See
+    /// `yabai::protocol::write_framed`. With the `log` feature, the bytes are logged as they're
+    /// sent, the same as messages sent with `send_args`.
+    ///
+    /// ```no_run
+    /// let client = yabai::YabaiClient::new();
+    /// client.send_framed(b"space\0--balance\0\0")?;
+    /// # Ok::<(), yabai::YabaiError>(())
+    /// ```
+    pub fn send_framed(&self, bytes: &[u8]) -> Result<Option<String>, YabaiError> {
+        self.send_raw(bytes, &describe_framed(bytes))
+    }
+
+    fn send_raw(&self, bytes: &[u8], command: &str) -> Result<Option<String>, YabaiError> {
+        if self.record(command.to_string()) {
+            return Ok(None);
+        }
+
+        let mut stream = self.connect()?;
+
+        stream.set_read_timeout(self.timeout)?;
+        stream.set_write_timeout(self.timeout)?;
+
+        #[cfg(feature = "log")]
+        log::debug!("sending message \"{}\"", bytes.escape_ascii());
+
+        write_framed(&mut stream, bytes)?;
+        read_command_response(&mut stream, command)
+    }
+
+    /// Connects to the yabai socket, retrying as set with `with_retry`.
+    ///
+    /// Each attempt is bounded by the timeout set with `with_timeout`.
+    fn connect(&self) -> Result<UnixStream, YabaiError> {
+        let path = self.socket_path()?;
+        let mut attempt = 0;
+
+        loop {
+            match connect_within(&path, self.timeout) {
+                Ok(stream) => return Ok(stream),
+                Err(error) if attempt < self.retries && is_retryable(&error) => {
+                    attempt += 1;
+                    std::thread::sleep(self.retry_delay);
+                }
+                Err(error) => return Err(map_connect_error(error, path)),
+            }
+        }
+    }
+
+    /// Send a `yabai::Command` to yabai.
+    ///
+    /// The command is validated with `Command::validate` before it is sent. Returns `None` when
+    /// yabai succeeds without a response, which is the case for every command other than
+    /// `Command::Query` and `Command::Custom`.
+    pub fn send_command(&self, command: &Command) -> Result<Option<String>, YabaiError> {
+        command.validate()?;
+
+        self.send_args(&command.to_args())
+    }
+
+    /// Sends each command to yabai, returning a result for each command.
+    ///
+    /// yabai accepts a single message per connection, so each command is sent over its own
+    /// connection. Commands are sent one after another in the order given, and each command is
+    /// sent once the previous one has completed. A failing command doesn't stop the remaining
+    /// commands from being sent:
+    ///
+    /// ```no_run
+    /// use yabai::{Command, WindowSelector};
+    ///
+    /// let client = yabai::YabaiClient::new();
+    /// let results = client.send_batch(&[
+    ///     Command::FocusWindow { window: WindowSelector::Next {} },
+    ///     Command::BalanceSpace {},
+    /// ]);
+    ///
+    /// for result in results {
+    ///     if let Err(error) = result {
+    ///         eprintln!("{error}");
+    ///     }
+    /// }
+    /// ```
+    pub fn send_batch(&self, commands: &[Command]) -> Vec<Result<Option<String>, YabaiError>> {
+        commands
+            .iter()
+            .map(|command| self.send_command(command))
+            .collect()
+    }
+
+    /// Returns the current value of a config setting.
+    pub fn get_config(&self, key: ConfigKey) -> Result<String, YabaiError> {
+        let key = key.to_string();
+
+        self.send_args(&["config", &key])?
+            .ok_or(YabaiError::EmptyResponse)
+    }
+
+    /// Returns the current value of an `on` or `off` config setting.
+    ///
+    /// Any other value is returned as a `YabaiError::FormatError` naming the key and value.
+    ///
+    /// ```no_run
+    /// let client = yabai::YabaiClient::new();
+    ///
+    /// if client.get_config_bool(yabai::ConfigKey::AutoBalance)? {
+    ///     println!("auto balance is on");
+    /// }
+    /// # Ok::<(), yabai::YabaiError>(())
+    /// ```
+    pub fn get_config_bool(&self, key: ConfigKey) -> Result<bool, YabaiError> {
+        match self.get_config(key)?.as_str() {
+            "on" => Ok(true),
+            "off" => Ok(false),
+            value => Err(config_format_error(key, value, "on or off")),
+        }
+    }
+
+    /// Returns the current value of a numeric config setting, such as `window_opacity_duration`.
+    ///
+    /// A value that isn't a number is returned as a `YabaiError::FormatError`.
+    pub fn get_config_f32(&self, key: ConfigKey) -> Result<f32, YabaiError> {
+        let value = self.get_config(key)?;
+
+        value
+            .parse()
+            .map_err(|_| config_format_error(key, &value, "a number"))
+    }
+
+    /// Returns the current value of a whole number config setting, such as `window_gap`.
+    ///
+    /// A value that isn't a whole number is returned as a `YabaiError::FormatError`.
+    pub fn get_config_u32(&self, key: ConfigKey) -> Result<u32, YabaiError> {
+        let value = self.get_config(key)?;
+
+        value
+            .parse()
+            .map_err(|_| config_format_error(key, &value, "a whole number"))
+    }
+
+    /// Returns the current value of a config setting for a single space.
+    pub fn get_space_config(
+        &self,
+        space: &SpaceSelector,
+        key: ConfigKey,
+    ) -> Result<String, YabaiError> {
+        let space = space.to_string();
+        let key = key.to_string();
+
+        self.send_args(&["config", "--space", &space, &key])?
+            .ok_or(YabaiError::EmptyResponse)
+    }
+
+    /// Sets a config setting for a single space.
+    pub fn set_space_config(
+        &self,
+        space: &SpaceSelector,
+        key: ConfigKey,
+        value: &str,
+    ) -> Result<Option<String>, YabaiError> {
+        self.send_command(&Command::SetSpaceConfig {
+            space: space.clone(),
+            key,
+            value: value.to_string(),
+        })
+    }
+
+    /// Sets a config setting.
+    pub fn set_config(&self, key: ConfigKey, value: &str) -> Result<Option<String>, YabaiError> {
+        self.send_command(&Command::SetConfig {
+            key,
+            value: value.to_string(),
+            space: None,
+        })
+    }
+
+    /// Sets whether the mouse moves to the center of windows as they're focused.
+    pub fn set_mouse_follows_focus(&self, enabled: bool) -> Result<Option<String>, YabaiError> {
+        self.set_config(ConfigKey::MouseFollowsFocus, on_off(enabled))
+    }
+
+    /// Sets whether windows are focused as the mouse moves over them, and whether they're raised.
+    pub fn set_focus_follows_mouse(
+        &self,
+        mode: FocusFollowsMouse,
+    ) -> Result<Option<String>, YabaiError> {
+        self.set_config(ConfigKey::FocusFollowsMouse, &mode.to_string())
+    }
+
+    /// Sets whether windows draw shadows.
+    ///
+    /// yabai can also draw shadows only for floating windows, which can be set with
+    /// `set_config(ConfigKey::WindowShadow, "float")`.
+    pub fn set_window_shadow(&self, enabled: bool) -> Result<Option<String>, YabaiError> {
+        self.set_config(ConfigKey::WindowShadow, on_off(enabled))
+    }
+
+    /// Sets whether the windows on a space are balanced automatically as windows are added or
+    /// removed.
+    pub fn set_auto_balance(&self, enabled: bool) -> Result<Option<String>, YabaiError> {
+        self.set_config(ConfigKey::AutoBalance, on_off(enabled))
+    }
+
+    /// Sets whether yabai draws borders around windows.
+    ///
+    /// yabai 6 removed window borders, so this fails with a `YabaiError::CommandError` there.
+    pub fn set_window_border(&self, enabled: bool) -> Result<Option<String>, YabaiError> {
+        self.set_config(ConfigKey::WindowBorder, on_off(enabled))
+    }
+
+    /// Sets the width of window borders, in points.
+    pub fn set_window_border_width(&self, width: u32) -> Result<Option<String>, YabaiError> {
+        self.set_config(ConfigKey::WindowBorderWidth, &width.to_string())
+    }
+
+    /// Sets the border color of the focused window.
+    ///
+    /// ```
+    /// let client = yabai::YabaiClient::dry_run();
+    /// client.set_active_window_border_color(yabai::Color(0xff775759))?;
+    ///
+    /// assert_eq!(
+    ///     client.recorded_messages(),
+    ///     ["config active_window_border_color 0xff775759"],
+    /// );
+    /// # Ok::<(), yabai::YabaiError>(())
+    /// ```
+    pub fn set_active_window_border_color(
+        &self,
+        color: Color,
+    ) -> Result<Option<String>, YabaiError> {
+        self.set_config(ConfigKey::ActiveWindowBorderColor, &color.to_string())
+    }
+
+    /// Sets the border color of windows that aren't focused.
+    pub fn set_normal_window_border_color(
+        &self,
+        color: Color,
+    ) -> Result<Option<String>, YabaiError> {
+        self.set_config(ConfigKey::NormalWindowBorderColor, &color.to_string())
+    }
+
+    /// Sets the color of the overlay shown where a window will be inserted.
+    pub fn set_insert_feedback_color(&self, color: Color) -> Result<Option<String>, YabaiError> {
+        self.set_config(ConfigKey::InsertFeedbackColor, &color.to_string())
+    }
+
+    pub fn focus_window(&self, window: WindowId) -> Result<Option<String>, YabaiError> {
+        self.send_command(&Command::FocusWindow {
+            window: WindowSelector::Id { id: window },
+        })
+    }
+
+    /// Focuses the selected window, falling back to focusing `fallback` if yabai can't find a
+    /// window to focus, such as when there's no window in the given direction.
+    ///
+    /// Only a `YabaiErrorKind::WindowNotFound` error triggers the fallback. Other errors are
+    /// returned as-is.
+    pub fn focus_window_or(
+        &self,
+        window: WindowSelector,
+        fallback: WindowSelector,
+    ) -> Result<Option<String>, YabaiError> {
+        match self.send_command(&Command::FocusWindow { window }) {
+            Err(error) if error.kind() == Some(YabaiErrorKind::WindowNotFound) => {
+                self.send_command(&Command::FocusWindow { window: fallback })
+            }
+            result => result,
+        }
+    }
+
+    /// Focuses the window under the mouse (`window --focus mouse`).
+    pub fn focus_mouse_window(&self) -> Result<Option<String>, YabaiError> {
+        self.send_command(&Command::FocusWindow {
+            window: WindowSelector::Mouse {},
+        })
+    }
+
+    /// Focuses the previously focused window (`window --focus recent`).
+    pub fn focus_recent_window(&self) -> Result<Option<String>, YabaiError> {
+        self.send_command(&Command::FocusWindow {
+            window: WindowSelector::Recent {},
+        })
+    }
+
+    pub fn focus_space(&self, space: SpaceIndex) -> Result<Option<String>, YabaiError> {
+        self.send_command(&Command::FocusSpace {
+            option: FocusSpaceOption::Space { space },
+        })
+    }
+
+    /// Moves the focused window to the selected space, then focuses that space so focus follows
+    /// the window.
+    ///
+    /// The space is only focused once the window has moved, so a window yabai can't move
+    /// returns the error without changing the focused space:
+    ///
+    /// ```
+    /// use yabai::{SpaceIndex, SpaceSelector};
+    ///
+    /// let client = yabai::YabaiClient::dry_run();
+    /// client.move_window_to_space_and_follow(SpaceSelector::Index { index: SpaceIndex(3) })?;
+    ///
+    /// assert_eq!(client.recorded_messages(), ["window --space 3", "space --focus 3"]);
+    /// # Ok::<(), yabai::YabaiError>(())
+    /// ```
+    pub fn move_window_to_space_and_follow(&self, space: SpaceSelector) -> Result<(), YabaiError> {
+        self.send_command(&Command::MoveActiveWindowToSpace {
+            space: space.clone(),
+        })?;
+        self.send_command(&Command::FocusSpace {
+            option: space.into(),
+        })?;
+
+        Ok(())
+    }
+
+    /// Toggles picture-in-picture for the focused window.
+    pub fn toggle_pip(&self) -> Result<Option<String>, YabaiError> {
+        self.send_command(&Command::ToggleWindow {
+            toggle: WindowToggle::Pip,
+        })
+    }
+
+    /// Toggles float, sticky, topmost and picture-in-picture for the focused window, in that
+    /// order, such as to pop a video out over other windows.
+    ///
+    /// Each property is toggled, so a window that already floats stops floating. The first
+    /// toggle yabai fails to apply is returned as an error, and the remaining toggles aren't
+    /// sent:
+    ///
+    /// ```
+    /// let client = yabai::YabaiClient::dry_run();
+    /// client.make_floating_overlay()?;
+    ///
+    /// assert_eq!(
+    ///     client.recorded_messages(),
+    ///     [
+    ///         "window --toggle float",
+    ///         "window --toggle sticky",
+    ///         "window --toggle topmost",
+    ///         "window --toggle pip",
+    ///     ],
+    /// );
+    /// # Ok::<(), yabai::YabaiError>(())
+    /// ```
+    pub fn make_floating_overlay(&self) -> Result<(), YabaiError> {
+        for toggle in [
+            WindowToggle::Float,
+            WindowToggle::Sticky,
+            WindowToggle::Topmost,
+            WindowToggle::Pip,
+        ] {
+            self.send_command(&Command::ToggleWindow { toggle })?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Creates the error returned when a config setting's value can't be parsed.
+fn config_format_error(key: ConfigKey, value: &str, expected: &str) -> YabaiError {
+    YabaiError::FormatError(format!(
+        "config {} returned {:?}, which isn't {}",
+        key, value, expected
+    ))
+}
+
+/// Converts a failure to connect to the yabai socket into an error, reporting a missing socket
+/// as a `YabaiError::SocketNotFound`.
+pub(crate) fn map_connect_error(error: io::Error, path: PathBuf) -> YabaiError {
+    match error.kind() {
+        io::ErrorKind::NotFound => YabaiError::SocketNotFound(path),
+        io::ErrorKind::TimedOut => YabaiError::Timeout,
+        _ => YabaiError::Io(error),
+    }
+}
+
+/// Connects to the socket at `path`, failing with `io::ErrorKind::TimedOut` if the connection
+/// isn't made within `timeout`.
+///
+/// The connection is made on its own thread, which is left to finish if it outlives the timeout.
+fn connect_within(path: &Path, timeout: Option<Duration>) -> io::Result<UnixStream> {
+    let Some(timeout) = timeout else {
+        return UnixStream::connect(path);
+    };
+
+    let (sender, receiver) = mpsc::channel();
+    let path = path.to_path_buf();
+
+    std::thread::spawn(move || {
+        let _ = sender.send(UnixStream::connect(path));
+    });
+
+    receiver.recv_timeout(timeout).unwrap_or_else(|_| {
+        Err(io::Error::new(
+            io::ErrorKind::TimedOut,
+            "timed out connecting to the yabai socket",
+        ))
+    })
+}
+
+/// Returns whether a failure to connect to the yabai socket may succeed when retried.
+pub(crate) fn is_retryable(error: &io::Error) -> bool {
+    matches!(
+        error.kind(),
+        io::ErrorKind::ConnectionRefused | io::ErrorKind::NotFound
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockYabai;
+    use std::os::unix::net::UnixListener;
+
+    #[test]
+    fn send_trims_the_trailing_newline() {
+        let mock = MockYabai::start(&[b"2\n"]).unwrap();
+
+        let gap = mock.client().send("config window_gap").unwrap();
+
+        assert_eq!(gap.as_deref(), Some("2"));
+        assert_eq!(mock.finish().unwrap(), ["config window_gap"]);
+    }
+
+    #[test]
+    fn send_args_replaces_invalid_utf8() {
+        let mock = MockYabai::start(&[b"caf\xff\n"]).unwrap();
+
+        let response = mock.client().send_args(&["config", "window_gap"]).unwrap();
+
+        assert_eq!(response.as_deref(), Some("caf\u{FFFD}"));
+        mock.finish().unwrap();
+    }
+
+    #[test]
+    fn send_batch_continues_after_a_failing_command() {
+        let mock =
+            MockYabai::start(&[b"\x07could not locate the selected window.\n", b""]).unwrap();
+
+        let results = mock.client().send_batch(&[
+            Command::FocusWindow {
+                window: WindowSelector::Next {},
+            },
+            Command::BalanceSpace {},
+        ]);
+
+        assert!(matches!(
+            &results[0],
+            Err(YabaiError::CommandError { command, .. }) if command == "window --focus next"
+        ));
+        assert!(matches!(results[1], Ok(None)));
+        assert_eq!(
+            mock.finish().unwrap(),
+            ["window --focus next", "space --balance"]
+        );
+    }
+
+    #[test]
+    fn get_config_bool_parses_on_and_off() {
+        let mock = MockYabai::start(&[b"on\n", b"off\n", b"float\n"]).unwrap();
+        let client = mock.client();
+
+        assert!(client.get_config_bool(ConfigKey::AutoBalance).unwrap());
+        assert!(!client.get_config_bool(ConfigKey::AutoBalance).unwrap());
+        assert!(matches!(
+            client.get_config_bool(ConfigKey::WindowShadow),
+            Err(YabaiError::FormatError(message))
+                if message == r#"config window_shadow returned "float", which isn't on or off"#
+        ));
+        mock.finish().unwrap();
+    }
+
+    #[test]
+    fn get_config_f32_parses_numbers() {
+        let mock = MockYabai::start(&[b"0.5\n", b"fast\n"]).unwrap();
+        let client = mock.client();
+
+        assert_eq!(
+            client
+                .get_config_f32(ConfigKey::WindowOpacityDuration)
+                .unwrap(),
+            0.5
+        );
+        assert!(matches!(
+            client.get_config_f32(ConfigKey::WindowOpacityDuration),
+            Err(YabaiError::FormatError(message))
+                if message == r#"config window_opacity_duration returned "fast", which isn't a number"#
+        ));
+        mock.finish().unwrap();
+    }
+
+    #[test]
+    fn get_config_u32_parses_whole_numbers() {
+        let mock = MockYabai::start(&[b"12\n", b"-4\n"]).unwrap();
+        let client = mock.client();
+
+        assert_eq!(client.get_config_u32(ConfigKey::WindowGap).unwrap(), 12);
+        assert!(matches!(
+            client.get_config_u32(ConfigKey::WindowGap),
+            Err(YabaiError::FormatError(message))
+                if message == r#"config window_gap returned "-4", which isn't a whole number"#
+        ));
+        mock.finish().unwrap();
+    }
+
+    #[test]
+    fn retries_give_up_on_a_missing_socket() {
+        let client = YabaiClient::with_socket_path("/nonexistent/yabai.socket")
+            .with_retry(2, Duration::from_millis(1));
+
+        assert!(matches!(
+            client.send("space --balance"),
+            Err(YabaiError::SocketNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn command_errors_are_not_retried() {
+        let mock = MockYabai::start(&[b"\x07cannot focus an already focused space.\n"]).unwrap();
+        let client = mock.client().with_retry(3, Duration::from_millis(1));
+
+        assert!(matches!(
+            client.send("space --focus 2"),
+            Err(YabaiError::CommandError { .. })
+        ));
+        assert_eq!(mock.finish().unwrap(), ["space --focus 2"]);
+    }
+
+    #[test]
+    fn timeout_elapses_when_yabai_does_not_respond() {
+        let path =
+            std::env::temp_dir().join(format!("yabai-timeout-test-{}.socket", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let _listener = UnixListener::bind(&path).unwrap();
+
+        let client = YabaiClient::with_socket_path(&path)
+            .with_timeout(Duration::from_millis(50))
+            .unwrap();
+        let result = client.send("query --spaces");
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(matches!(result, Err(YabaiError::Timeout)));
+    }
+}
@@ -0,0 +1,136 @@
+use crate::{protocol::encode_args, YabaiClient, YabaiError};
+use std::fmt;
+
+/// A builder for commands sent to yabai as a list of arguments.
+///
+/// Each argument is sent as-is, so values containing spaces, quotes, or regex syntax don't
+/// need any quoting or escaping. The builder can be cloned and sent more than once.
+///
+/// ```
+/// let builder = yabai::CommandBuilder::new()
+///     .arg("rule")
+///     .arg("--add")
+///     .kv("app", "Google Chrome");
+///
+/// assert_eq!(builder.to_args(), ["rule", "--add", "app=Google Chrome"]);
+/// assert_eq!(
+///     builder.to_wire_bytes(),
+///     b"\x1e\0\0\0rule\0--add\0app=Google Chrome\0\0".to_vec()
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CommandBuilder {
+    args: Vec<String>,
+}
+
+impl CommandBuilder {
+    /// Creates a builder with no arguments.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends an argument.
+    pub fn arg(mut self, arg: impl Into<String>) -> Self {
+        self.args.push(arg.into());
+        self
+    }
+
+    /// Appends each of the given arguments.
+    pub fn args<I, S>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.args.extend(args.into_iter().map(Into::into));
+        self
+    }
+
+    /// Appends a `key=value` argument, as used by `rule --add` and `signal --add`.
+    pub fn kv(self, key: &str, value: impl AsRef<str>) -> Self {
+        self.arg(KeyValue::new(key, value.as_ref()))
+    }
+
+    /// Returns the arguments added so far.
+    pub fn to_args(&self) -> &[String] {
+        &self.args
+    }
+
+    /// Returns the bytes written to the yabai socket for this command: the length of the
+    /// message as a little-endian `u32`, followed by each argument terminated with a NUL byte,
+    /// and a final NUL byte.
+    pub fn to_wire_bytes(&self) -> Vec<u8> {
+        let args = self.args.iter().map(String::as_str).collect::<Vec<&str>>();
+        let message = encode_args(&args);
+
+        let mut bytes = (message.len() as u32).to_le_bytes().to_vec();
+        bytes.extend_from_slice(message.as_bytes());
+        bytes
+    }
+
+    /// Sends the command to yabai using the default client.
+    pub fn send(&self) -> Result<Option<String>, YabaiError> {
+        self.send_with(&YabaiClient::new())
+    }
+
+    /// Sends the command to yabai using the given client.
+    pub fn send_with(&self, client: &YabaiClient) -> Result<Option<String>, YabaiError> {
+        client.send_args(&self.args)
+    }
+}
+
+/// A `key=value` argument, as used by `rule --add` and `signal --add`.
+///
+/// The key and value are sent as a single argument, so the value may contain spaces and `=`
+/// signs without being split:
+///
+/// ```
+/// use yabai::{CommandBuilder, KeyValue};
+///
+/// let builder = CommandBuilder::new()
+///     .args(["rule", "--add"])
+///     .arg(KeyValue::new("app", "^Safari$"))
+///     .arg(KeyValue::new("title", "a = b"))
+///     .arg(KeyValue::new("manage", "off"));
+///
+/// assert_eq!(
+///     builder.to_args(),
+///     ["rule", "--add", "app=^Safari$", "title=a = b", "manage=off"]
+/// );
+/// assert_eq!(
+///     &builder.to_wire_bytes()[4..],
+///     b"rule\0--add\0app=^Safari$\0title=a = b\0manage=off\0\0"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KeyValue {
+    pub key: String,
+    pub value: String,
+}
+
+impl KeyValue {
+    /// Creates a `key=value` argument.
+    pub fn new(key: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            value: value.into(),
+        }
+    }
+}
+
+impl fmt::Display for KeyValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}={}", self.key, self.value)
+    }
+}
+
+impl From<KeyValue> for String {
+    fn from(key_value: KeyValue) -> Self {
+        key_value.to_string()
+    }
+}
+
+impl<K: Into<String>, V: Into<String>> From<(K, V)> for KeyValue {
+    fn from((key, value): (K, V)) -> Self {
+        KeyValue::new(key, value)
+    }
+}
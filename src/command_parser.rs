@@ -0,0 +1,711 @@
+use crate::{
+    protocol::split_message, Command, ConfigKey, DisplayIndex, Event, Grid, RelAbs, Rule,
+    SpaceIndex, WindowToggle, YabaiError,
+};
+use std::str::FromStr;
+
+/// The rule properties with a field on `Rule`.
+const RULE_PROPERTIES: &[&str] = &[
+    "label", "app", "title", "space", "display", "opacity", "manage", "sticky",
+];
+
+/// The rule properties yabai accepts that `Rule` doesn't model.
+const UNMODELED_RULE_PROPERTIES: &[&str] = &[
+    "role",
+    "subrole",
+    "follow_space",
+    "mouse_follows_focus",
+    "layer",
+    "native-fullscreen",
+    "grid",
+    "scratchpad",
+    "one-shot",
+];
+
+/// The signal properties with a field on `Command::AddSignal`.
+const SIGNAL_PROPERTIES: &[&str] = &["event", "action", "label"];
+
+/// The signal properties yabai accepts that `Command::AddSignal` doesn't model.
+const UNMODELED_SIGNAL_PROPERTIES: &[&str] = &["app", "title", "active"];
+
+impl Command {
+    /// Parses a message of space-separated arguments into the command that sends it.
+    ///
+    /// This is the inverse of formatting a command with `to_string`, apart from the exceptions
+    /// listed below. Messages that don't match a modeled command are returned as a
+    /// `Command::Custom`, while modeled commands with invalid values return a
+    /// `YabaiError::InvalidArgument`.
+    ///
+    /// ```
+    /// use yabai::{Command, FocusSpaceOption, SpaceIndex};
+    ///
+    /// assert_eq!(
+    ///     Command::from_message("space --focus 2")?,
+    ///     Command::FocusSpace {
+    ///         option: FocusSpaceOption::Space { space: SpaceIndex(2) },
+    ///     }
+    /// );
+    /// assert_eq!(
+    ///     Command::from_message("query --spaces --space 2")?,
+    ///     Command::Custom {
+    ///         message: "query --spaces --space 2".to_string(),
+    ///     }
+    /// );
+    /// assert!(Command::from_message("window --opacity lots").is_err());
+    /// # Ok::<(), yabai::YabaiError>(())
+    /// ```
+    ///
+    /// `Command::from_message(&command.to_string())` returns `command` for every variant, with
+    /// these exceptions:
+    ///
+    /// - The aliases `FocusWindowDirection`, `SwapWindowDirection`, `WarpWindowDirection`,
+    ///   `ToggleWindowFloating` and `ToggleZoomFullscreen`, and `SetConfig` with a space, parse
+    ///   into the commands they stand for.
+    /// - A `ToggleScratchpad` whose name is a `WindowToggle`, such as `float`, parses as a
+    ///   `ToggleWindow`, as both send `window --toggle`.
+    /// - A `Custom` message that matches a modeled command parses as that command.
+    /// - Labels, names and other free-form values parse as whatever their text matches. A
+    ///   selector label of `2` or `next` parses as that index or selector, and whitespace at
+    ///   the end of the message is trimmed.
+    ///
+    /// ```
+    /// use yabai::{Command, WindowToggle};
+    ///
+    /// let command = Command::ToggleScratchpad { name: "music player".to_string() };
+    /// assert_eq!(Command::from_message(&command.to_string())?, command);
+    ///
+    /// let command = Command::ToggleScratchpad { name: "float".to_string() };
+    /// assert_eq!(
+    ///     Command::from_message(&command.to_string())?,
+    ///     Command::ToggleWindow { toggle: WindowToggle::Float }
+    /// );
+    ///
+    /// let command = Command::Custom { message: "space --balance".to_string() };
+    /// assert_eq!(Command::from_message(&command.to_string())?, Command::BalanceSpace {});
+    /// # Ok::<(), yabai::YabaiError>(())
+    /// ```
+    pub fn from_message(message: &str) -> Result<Command, YabaiError> {
+        let args = split_message(message);
+
+        match parse_args(&args)? {
+            Some(command) => Ok(command),
+            None => Ok(Command::Custom {
+                message: message.trim().to_string(),
+            }),
+        }
+    }
+}
+
+/// Parses the arguments of a message, returning `None` for messages that aren't modeled.
+fn parse_args(args: &[&str]) -> Result<Option<Command>, YabaiError> {
+    let command = match args {
+        ["space", "--focus", option @ ..] if !option.is_empty() => Command::FocusSpace {
+            option: parse(&option.join(" "))?,
+        },
+        ["space", "--rotate", rotation] => Command::RotateSpace {
+            rotation: parse(rotation)?,
+        },
+        ["space", "--balance"] => Command::BalanceSpace {},
+        ["space", "--mirror", axis] => Command::MirrorSpace { axis: parse(axis)? },
+        ["space", "--equalize"] => Command::EqualizeSpace {},
+        ["window", "--space", space @ ..] if !space.is_empty() => {
+            Command::MoveActiveWindowToSpace {
+                space: parse(&space.join(" "))?,
+            }
+        }
+        ["window", "--focus", window] => Command::FocusWindow {
+            window: parse(window)?,
+        },
+        ["window", "--swap", window] => Command::SwapWindow {
+            window: parse(window)?,
+        },
+        ["window", "--warp", window] => Command::WarpWindow {
+            window: parse(window)?,
+        },
+        ["window", "--grid", grid] => Command::from(grid.parse::<Grid>()?),
+        ["window", "--move", position] => Command::MoveWindow {
+            position: parse(position)?,
+        },
+        ["window", "--resize", resize] => match resize.split(':').collect::<Vec<&str>>()[..] {
+            [handle, dx, dy] => Command::ResizeWindow {
+                handle: parse(handle)?,
+                dx: parse(dx)?,
+                dy: parse(dy)?,
+            },
+            _ => return Err(invalid(resize)),
+        },
+        ["window", "--opacity", opacity] => Command::SetWindowOpacity {
+            opacity: parse(opacity)?,
+        },
+        ["window", window, "--opacity", opacity] if !window.starts_with("--") => {
+            Command::SetSelectedWindowOpacity {
+                window: parse(window)?,
+                opacity: parse(opacity)?,
+            }
+        }
+        ["window", "--insert", direction] => Command::InsertWindow {
+            direction: parse(direction)?,
+        },
+        ["space", "--create"] => Command::CreateSpace {},
+        ["space", "--create", display] => match display.parse::<u32>() {
+            Ok(index) => Command::CreateSpaceOnDisplay {
+                display: DisplayIndex(index),
+            },
+            Err(_) => return Ok(None),
+        },
+        ["space", "--destroy", space] => match space.parse::<u32>() {
+            Ok(index) => Command::DestroySpace {
+                space: SpaceIndex(index),
+            },
+            Err(_) => return Ok(None),
+        },
+        ["space", "--move", space] => match space.parse::<u32>() {
+            Ok(index) => Command::MoveSpace {
+                space: SpaceIndex(index),
+            },
+            Err(_) => return Ok(None),
+        },
+        ["space", space, "--move", to] if !space.starts_with("--") => match to.parse::<u32>() {
+            Ok(index) => Command::MoveSelectedSpace {
+                space: parse(space)?,
+                to: SpaceIndex(index),
+            },
+            Err(_) => return Ok(None),
+        },
+        ["space", "--swap", space] => match space.parse::<u32>() {
+            Ok(index) => Command::SwapSpace {
+                space: SpaceIndex(index),
+            },
+            Err(_) => return Ok(None),
+        },
+        ["space", "--label", label @ ..] => Command::LabelSpace {
+            label: label.join(" "),
+        },
+        ["space", "--layout", layout] => Command::SetSpaceLayout {
+            layout: parse(layout)?,
+        },
+        ["space", "--padding", padding] => match padding.split(':').collect::<Vec<&str>>()[..] {
+            [mode, top, bottom, left, right] => Command::SetSpacePadding {
+                mode: parse(mode)?,
+                top: parse(top)?,
+                bottom: parse(bottom)?,
+                left: parse(left)?,
+                right: parse(right)?,
+            },
+            _ => return Err(invalid(padding)),
+        },
+        ["space", "--gap", gap] => {
+            let (mode, amount) = parse_rel_abs(gap)?;
+
+            Command::SetSpaceGap { mode, amount }
+        }
+        ["display", "--focus", display @ ..] if !display.is_empty() => Command::FocusDisplay {
+            display: parse(&display.join(" "))?,
+        },
+        ["window", "--display", display] => match display.parse::<u32>() {
+            Ok(index) => Command::MoveWindowToDisplay {
+                display: DisplayIndex(index),
+            },
+            Err(_) => return Ok(None),
+        },
+        ["window", "--toggle", toggle @ ..] if !toggle.is_empty() => {
+            let toggle = toggle.join(" ");
+
+            match toggle.parse::<WindowToggle>() {
+                Ok(toggle) => Command::ToggleWindow { toggle },
+                Err(_) => Command::ToggleScratchpad { name: toggle },
+            }
+        }
+        ["window", "--close"] => Command::CloseWindow { window: None },
+        ["window", "--close", window] => Command::CloseWindow {
+            window: Some(parse(window)?),
+        },
+        ["window", "--minimize"] => Command::MinimizeWindow { window: None },
+        ["window", "--minimize", window] => Command::MinimizeWindow {
+            window: Some(parse(window)?),
+        },
+        ["window", "--raise"] => Command::RaiseWindow { window: None },
+        ["window", "--raise", window] => Command::RaiseWindow {
+            window: Some(parse(window)?),
+        },
+        ["window", "--deminimize", window] => Command::DeminimizeWindow {
+            window: parse(window)?,
+        },
+        ["window", "--stack", window] => Command::StackWindow {
+            window: parse(window)?,
+        },
+        ["signal", "--add", properties @ ..] => {
+            let Some(properties) =
+                parse_properties(properties, SIGNAL_PROPERTIES, UNMODELED_SIGNAL_PROPERTIES)
+            else {
+                return Ok(None);
+            };
+
+            let mut event = None;
+            let mut action = None;
+            let mut label = None;
+
+            for (key, value) in properties {
+                match key {
+                    "event" => event = Some(parse::<Event>(&value)?),
+                    "action" => action = Some(value),
+                    _ => label = Some(value),
+                }
+            }
+
+            match (event, action) {
+                (Some(event), Some(action)) => Command::AddSignal {
+                    event,
+                    action,
+                    label,
+                },
+                _ => {
+                    return Err(YabaiError::InvalidArgument(
+                        "signal --add requires an event and an action".to_string(),
+                    ))
+                }
+            }
+        }
+        ["signal", "--remove", label @ ..] if !label.is_empty() => Command::RemoveSignal {
+            label: label.join(" "),
+        },
+        ["rule", "--add", properties @ ..] => {
+            let Some(properties) =
+                parse_properties(properties, RULE_PROPERTIES, UNMODELED_RULE_PROPERTIES)
+            else {
+                return Ok(None);
+            };
+
+            let mut rule = Rule::new();
+
+            for (key, value) in properties {
+                rule = match key {
+                    "label" => rule.label(value),
+                    "app" => rule.app(value),
+                    "title" => rule.title(value),
+                    "space" => rule.space(parse(&value)?),
+                    "display" => rule.display(parse(&value)?),
+                    "opacity" => rule.opacity(parse(&value)?),
+                    "manage" => rule.manage(parse_on_off(&value)?),
+                    _ => rule.sticky(parse_on_off(&value)?),
+                };
+            }
+
+            Command::AddRule { rule }
+        }
+        ["rule", "--remove", rule @ ..] if !rule.is_empty() => Command::RemoveRule {
+            rule: rule.join(" "),
+        },
+        ["config", "--space", space, key, value @ ..] if !value.is_empty() => {
+            match key.parse::<ConfigKey>() {
+                Ok(key) => Command::SetSpaceConfig {
+                    space: parse(space)?,
+                    key,
+                    value: value.join(" "),
+                },
+                Err(_) => return Ok(None),
+            }
+        }
+        ["config", key, value @ ..] if !value.is_empty() => match key.parse::<ConfigKey>() {
+            Ok(key) => Command::SetConfig {
+                key,
+                value: value.join(" "),
+                space: None,
+            },
+            Err(_) => return Ok(None),
+        },
+        ["window", "--ratio", ratio] => {
+            let (mode, value) = parse_rel_abs(ratio)?;
+
+            Command::SetWindowRatio { mode, value }
+        }
+        ["window", "--layer", layer] => Command::SetWindowLayer {
+            layer: parse(layer)?,
+        },
+        ["window", "--scratchpad", name @ ..] => Command::SetScratchpad {
+            name: name.join(" "),
+        },
+        ["space", "--display", display @ ..] if !display.is_empty() => {
+            Command::MoveSpaceToDisplay {
+                display: parse(&display.join(" "))?,
+            }
+        }
+        ["query", kind] => match kind.strip_prefix("--").map(str::parse) {
+            Some(Ok(kind)) => Command::Query { kind },
+            _ => return Ok(None),
+        },
+        ["space", "--toggle", toggle] => Command::ToggleSpace {
+            toggle: parse(toggle)?,
+        },
+        _ => return Ok(None),
+    };
+
+    Ok(Some(command))
+}
+
+/// Parses a single value of a command.
+fn parse<T: FromStr>(value: &str) -> Result<T, YabaiError> {
+    value.parse().map_err(|_| invalid(value))
+}
+
+fn invalid(value: &str) -> YabaiError {
+    YabaiError::InvalidArgument(format!("invalid value {value:?}"))
+}
+
+/// Parses a value of the `rel:amount` or `abs:amount` form.
+fn parse_rel_abs(value: &str) -> Result<(RelAbs, f32), YabaiError> {
+    let (mode, amount) = value.split_once(':').ok_or_else(|| invalid(value))?;
+
+    Ok((parse(mode)?, parse(amount)?))
+}
+
+fn parse_on_off(value: &str) -> Result<bool, YabaiError> {
+    match value {
+        "on" => Ok(true),
+        "off" => Ok(false),
+        _ => Err(invalid(value)),
+    }
+}
+
+/// Parses `key=value` properties, as used by `rule --add` and `signal --add`.
+///
+/// Values may contain spaces, so arguments that don't start with a known key are joined onto
+/// the value before them. Returns `None` if an unmodeled property is given, or if the first
+/// argument isn't a property.
+fn parse_properties<'a>(
+    args: &[&'a str],
+    modeled: &[&str],
+    unmodeled: &[&str],
+) -> Option<Vec<(&'a str, String)>> {
+    let mut properties: Vec<(&'a str, String)> = Vec::new();
+
+    for arg in args {
+        match arg.split_once('=') {
+            Some((key, _)) if unmodeled.contains(&key) => return None,
+            Some((key, value)) if modeled.contains(&key) => {
+                properties.push((key, value.to_string()));
+            }
+            _ => {
+                let (_, value) = properties.last_mut()?;
+                value.push(' ');
+                value.push_str(arg);
+            }
+        }
+    }
+
+    Some(properties)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn modeled_commands_round_trip() {
+        let commands = vec![
+            Command::FocusSpace {
+                option: FocusSpaceOption::Recent {},
+            },
+            Command::FocusSpace {
+                option: FocusSpaceOption::Label {
+                    label: "comms".to_string(),
+                },
+            },
+            Command::RotateSpace {
+                rotation: SpaceRotation::Rotate90,
+            },
+            Command::BalanceSpace {},
+            Command::MirrorSpace {
+                axis: MirrorAxis::YAxis,
+            },
+            Command::EqualizeSpace {},
+            Command::MoveActiveWindowToSpace {
+                space: SpaceSelector::Index {
+                    index: SpaceIndex(3),
+                },
+            },
+            Command::FocusWindow {
+                window: WindowSelector::Id { id: WindowId(42) },
+            },
+            Command::SwapWindow {
+                window: Direction::West.into(),
+            },
+            Command::WarpWindow {
+                window: WindowSelector::Largest {},
+            },
+            Command::GridWindow {
+                rows: 4,
+                cols: 4,
+                start_x: 1,
+                start_y: 1,
+                width: 2,
+                height: 2,
+            },
+            Command::MoveWindow {
+                position: MovePosition::Relative { dx: -20.0, dy: 0.5 },
+            },
+            Command::ResizeWindow {
+                handle: ResizeHandle::TopLeft,
+                dx: -10.0,
+                dy: 10.0,
+            },
+            Command::SetWindowOpacity { opacity: 0.75 },
+            Command::SetSelectedWindowOpacity {
+                window: WindowSelector::Id { id: WindowId(42) },
+                opacity: 0.5,
+            },
+            Command::InsertWindow {
+                direction: InsertDirection::Stack,
+            },
+            Command::CreateSpace {},
+            Command::CreateSpaceOnDisplay {
+                display: DisplayIndex(2),
+            },
+            Command::DestroySpace {
+                space: SpaceIndex(4),
+            },
+            Command::MoveSpace {
+                space: SpaceIndex(1),
+            },
+            Command::MoveSelectedSpace {
+                space: SpaceSelector::Label {
+                    label: "code".to_string(),
+                },
+                to: SpaceIndex(3),
+            },
+            Command::SwapSpace {
+                space: SpaceIndex(2),
+            },
+            Command::LabelSpace {
+                label: "my space".to_string(),
+            },
+            Command::LabelSpace {
+                label: String::new(),
+            },
+            Command::SetSpaceLayout {
+                layout: Layout::Stack,
+            },
+            Command::SetSpacePadding {
+                mode: RelAbs::Abs,
+                top: 10.0,
+                bottom: 10.0,
+                left: 5.0,
+                right: 5.0,
+            },
+            Command::SetSpaceGap {
+                mode: RelAbs::Rel,
+                amount: -2.0,
+            },
+            Command::FocusDisplay {
+                display: DisplaySelector::Recent {},
+            },
+            Command::MoveWindowToDisplay {
+                display: DisplayIndex(1),
+            },
+            Command::ToggleWindow {
+                toggle: WindowToggle::ZoomParent,
+            },
+            Command::CloseWindow { window: None },
+            Command::MinimizeWindow {
+                window: Some(WindowSelector::Mouse {}),
+            },
+            Command::DeminimizeWindow {
+                window: WindowSelector::Id { id: WindowId(7) },
+            },
+            Command::StackWindow {
+                window: WindowSelector::Next {},
+            },
+            Command::AddSignal {
+                event: Event::WindowFocused,
+                action: "echo focused $YABAI_WINDOW_ID".to_string(),
+                label: Some("log-focus".to_string()),
+            },
+            Command::RemoveSignal {
+                label: "log-focus".to_string(),
+            },
+            Command::AddRule {
+                rule: Rule::new()
+                    .label("settings")
+                    .app("^System Settings$")
+                    .manage(false),
+            },
+            Command::RemoveRule {
+                rule: "settings".to_string(),
+            },
+            Command::SetConfig {
+                key: ConfigKey::WindowGap,
+                value: "10".to_string(),
+                space: None,
+            },
+            Command::SetSpaceConfig {
+                space: SpaceSelector::Label {
+                    label: "comms".to_string(),
+                },
+                key: ConfigKey::Layout,
+                value: "float".to_string(),
+            },
+            Command::SetWindowRatio {
+                mode: RelAbs::Rel,
+                value: -0.05,
+            },
+            Command::SetWindowLayer {
+                layer: Layer::Above,
+            },
+            Command::SetScratchpad {
+                name: "notes".to_string(),
+            },
+            Command::ToggleScratchpad {
+                name: "music player".to_string(),
+            },
+            Command::ToggleSpace {
+                toggle: SpaceToggle::ShowDesktop,
+            },
+            Command::MoveSpaceToDisplay {
+                display: DisplaySelector::Next {},
+            },
+            Command::RaiseWindow { window: None },
+            Command::RaiseWindow {
+                window: Some(WindowSelector::Recent {}),
+            },
+            Command::Query {
+                kind: QueryKind::Windows,
+            },
+            Command::Custom {
+                message: "query --windows --space 2".to_string(),
+            },
+        ];
+
+        for command in commands {
+            assert_eq!(
+                Command::from_message(&command.to_string()).unwrap(),
+                command
+            );
+
+            #[cfg(feature = "serde")]
+            {
+                let json = serde_json::to_string(&command).unwrap();
+                assert_eq!(serde_json::from_str::<Command>(&json).unwrap(), command);
+            }
+        }
+    }
+
+    #[test]
+    fn aliases_parse_into_the_commands_they_stand_for() {
+        let aliases = vec![
+            (
+                Command::FocusWindowDirection {
+                    direction: Direction::North,
+                },
+                Command::FocusWindow {
+                    window: Direction::North.into(),
+                },
+            ),
+            (
+                Command::SwapWindowDirection {
+                    direction: Direction::West,
+                },
+                Command::SwapWindow {
+                    window: Direction::West.into(),
+                },
+            ),
+            (
+                Command::WarpWindowDirection {
+                    direction: Direction::East,
+                },
+                Command::WarpWindow {
+                    window: Direction::East.into(),
+                },
+            ),
+            (
+                Command::ToggleWindowFloating {},
+                Command::ToggleWindow {
+                    toggle: WindowToggle::Float,
+                },
+            ),
+            (
+                Command::ToggleZoomFullscreen {},
+                Command::ToggleWindow {
+                    toggle: WindowToggle::ZoomFullscreen,
+                },
+            ),
+            (
+                Command::SetConfig {
+                    key: ConfigKey::Layout,
+                    value: "bsp".to_string(),
+                    space: Some(SpaceSelector::Label {
+                        label: "code".to_string(),
+                    }),
+                },
+                Command::SetSpaceConfig {
+                    space: SpaceSelector::Label {
+                        label: "code".to_string(),
+                    },
+                    key: ConfigKey::Layout,
+                    value: "bsp".to_string(),
+                },
+            ),
+        ];
+
+        for (alias, command) in aliases {
+            assert_eq!(alias.to_string(), command.to_string());
+            assert_eq!(Command::from_message(&alias.to_string()).unwrap(), command);
+        }
+    }
+
+    #[test]
+    fn scratchpads_named_like_window_toggles_parse_as_toggle_window() {
+        let command = Command::ToggleScratchpad {
+            name: "sticky".to_string(),
+        };
+
+        assert_eq!(
+            Command::from_message(&command.to_string()).unwrap(),
+            Command::ToggleWindow {
+                toggle: WindowToggle::Sticky,
+            }
+        );
+    }
+
+    #[test]
+    fn custom_messages_matching_a_modeled_command_parse_as_it() {
+        let command = Command::Custom {
+            message: "window --focus recent".to_string(),
+        };
+
+        assert_eq!(
+            Command::from_message(&command.to_string()).unwrap(),
+            Command::FocusWindow {
+                window: WindowSelector::Recent {},
+            }
+        );
+    }
+
+    #[test]
+    fn labels_matching_other_tokens_parse_as_them() {
+        let command = Command::FocusSpace {
+            option: FocusSpaceOption::Label {
+                label: "2".to_string(),
+            },
+        };
+
+        assert_eq!(
+            Command::from_message(&command.to_string()).unwrap(),
+            Command::FocusSpace {
+                option: FocusSpaceOption::Space {
+                    space: SpaceIndex(2),
+                },
+            }
+        );
+
+        let command = Command::LabelSpace {
+            label: "code ".to_string(),
+        };
+
+        assert_eq!(
+            Command::from_message(&command.to_string()).unwrap(),
+            Command::LabelSpace {
+                label: "code".to_string(),
+            }
+        );
+    }
+}
@@ -1,30 +1,770 @@
-use serde::{Deserialize, Serialize};
-use strum_macros::Display;
+use crate::{
+    protocol::split_message, ConfigKey, DisplayIndex, DisplaySelector, Event, Rule, SpaceIndex,
+    SpaceSelector, WindowId, WindowSelector, YabaiError,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize};
+use std::{
+    cmp::Ordering,
+    fmt,
+    ops::{Deref, DerefMut},
+    str::FromStr,
+};
+use strum_macros::{Display, EnumString};
 
 #[cfg(feature = "python")]
 use pyo3::prelude::*;
 
 /// An **enum** representing a command that can be sent to yabai.
 ///
-/// Used with the `yabai::send_command` function.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
+/// Used with the `yabai::send_command` function. yabai doesn't respond to any of these
+/// commands when they succeed, so sending them returns `Ok(None)`. Only a `Command::Query` or
+/// a `Command::Custom` message can return data; the typed `query_*` functions and
+/// `yabai::send_query` deserialize those responses directly.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 #[cfg_attr(feature = "python", pyclass)]
 pub enum Command {
-    FocusSpace { option: FocusSpaceOption },
-    RotateSpace { rotation: SpaceRotation },
+    FocusSpace {
+        option: FocusSpaceOption,
+    },
+    RotateSpace {
+        rotation: SpaceRotation,
+    },
     BalanceSpace {},
-    MoveActiveWindowToSpace { space: u32 },
-    FocusWindow { window: u32 },
-    FocusWindowDirection { direction: Direction },
-    SwapWindowDirection { direction: Direction },
-    WarpWindowDirection { direction: Direction },
+    /// Flips the layout of the focused space along an axis (`space --mirror`).
+    MirrorSpace {
+        axis: MirrorAxis,
+    },
+    /// Resets the split ratios of the focused space to their default (`space --equalize`).
+    EqualizeSpace {},
+    /// Moves the focused window to a space (`window --space`).
+    ///
+    /// ```
+    /// let command = yabai::Command::MoveActiveWindowToSpace {
+    ///     space: yabai::SpaceSelector::Label {
+    ///         label: "comms".to_string(),
+    ///     },
+    /// };
+    /// assert_eq!(command.to_args(), vec!["window", "--space", "comms"]);
+    /// ```
+    MoveActiveWindowToSpace {
+        space: SpaceSelector,
+    },
+    FocusWindow {
+        window: WindowSelector,
+    },
+    FocusWindowDirection {
+        direction: Direction,
+    },
+    /// An alias for `Command::SwapWindow` with a `WindowSelector::Direction`, kept for
+    /// compatibility.
+    SwapWindowDirection {
+        direction: Direction,
+    },
+    /// An alias for `Command::WarpWindow` with a `WindowSelector::Direction`, kept for
+    /// compatibility.
+    WarpWindowDirection {
+        direction: Direction,
+    },
+    /// Swaps the focused window with the selected window (`window --swap`).
+    ///
+    /// Directions convert into selectors, so either can be given:
+    ///
+    /// ```
+    /// use yabai::{Command, Direction, WindowId};
+    ///
+    /// let by_id = Command::SwapWindow { window: WindowId(42).into() };
+    /// assert_eq!(by_id.to_args(), vec!["window", "--swap", "42"]);
+    ///
+    /// let by_direction = Command::SwapWindow { window: Direction::West.into() };
+    /// assert_eq!(by_direction.to_args(), vec!["window", "--swap", "west"]);
+    /// ```
+    SwapWindow {
+        window: WindowSelector,
+    },
+    /// Warps the focused window into the selected window's node (`window --warp`).
+    WarpWindow {
+        window: WindowSelector,
+    },
+    /// An alias for `Command::ToggleWindow { toggle: WindowToggle::Float }`, kept for compatibility.
     ToggleWindowFloating {},
+    /// An alias for `Command::ToggleWindow { toggle: WindowToggle::ZoomFullscreen }`, kept for compatibility.
     ToggleZoomFullscreen {},
+    /// Places the focused window on a virtual grid (`window --grid`).
+    ///
+    /// The placement is validated before sending; see [`Grid::new`].
+    GridWindow {
+        rows: u32,
+        cols: u32,
+        start_x: u32,
+        start_y: u32,
+        width: u32,
+        height: u32,
+    },
+    /// Moves the focused floating window on screen (`window --move`).
+    MoveWindow {
+        position: MovePosition,
+    },
+    /// Resizes the focused floating window by moving one of its edges or corners (`window --resize`).
+    ///
+    /// With [`ResizeHandle::Abs`], `dx` and `dy` are the absolute width and height instead.
+    ResizeWindow {
+        handle: ResizeHandle,
+        dx: f32,
+        dy: f32,
+    },
+    /// Sets the opacity of the focused window (`window --opacity`).
+    ///
+    /// The opacity must be within `0.0..=1.0`. An opacity of `0.0` resets the window
+    /// to the opacity configured through yabai's `window_opacity` settings.
+    SetWindowOpacity {
+        opacity: f32,
+    },
+    /// Sets the opacity of the selected window (`window <WINDOW_SEL> --opacity`).
+    ///
+    /// The opacity is validated the same as for `Command::SetWindowOpacity`:
+    ///
+    /// ```
+    /// use yabai::{Command, WindowId, WindowSelector};
+    ///
+    /// let command = Command::SetSelectedWindowOpacity {
+    ///     window: WindowSelector::Id { id: WindowId(42) },
+    ///     opacity: 0.8,
+    /// };
+    /// assert_eq!(command.to_string(), "window 42 --opacity 0.8");
+    ///
+    /// let command = Command::SetSelectedWindowOpacity {
+    ///     window: WindowSelector::Id { id: WindowId(42) },
+    ///     opacity: 1.5,
+    /// };
+    /// assert!(command.validate().is_err());
+    /// ```
+    SetSelectedWindowOpacity {
+        window: WindowSelector,
+        opacity: f32,
+    },
+    /// Sets where the next window will be inserted relative to the focused window (`window --insert`).
+    InsertWindow {
+        direction: InsertDirection,
+    },
+    /// Creates a new space on the focused display (`space --create`).
+    CreateSpace {},
+    /// Creates a new space on the display with the given index (`space --create`).
+    CreateSpaceOnDisplay {
+        display: DisplayIndex,
+    },
+    /// Destroys the space with the given index (`space --destroy`).
+    DestroySpace {
+        space: SpaceIndex,
+    },
+    /// Moves the focused space to the position of the space with the given index (`space --move`).
+    MoveSpace {
+        space: SpaceIndex,
+    },
+    /// Moves the selected space to the position of the space with the given index
+    /// (`space <SPACE_SEL> --move`).
+    ///
+    /// ```
+    /// let command = yabai::Command::MoveSelectedSpace {
+    ///     space: yabai::SpaceSelector::Label {
+    ///         label: "code".to_string(),
+    ///     },
+    ///     to: yabai::SpaceIndex(1),
+    /// };
+    ///
+    /// assert_eq!(command.to_string(), "space code --move 1");
+    /// ```
+    MoveSelectedSpace {
+        space: SpaceSelector,
+        to: SpaceIndex,
+    },
+    /// Swaps the focused space with the space at the given index (`space --swap`).
+    SwapSpace {
+        space: SpaceIndex,
+    },
+    /// Labels the focused space (`space --label`).
+    ///
+    /// The label is sent as a single argument, so it may contain spaces. An empty label
+    /// removes the space's current label.
+    ///
+    /// ```
+    /// let command = yabai::Command::LabelSpace {
+    ///     label: "dev work".to_string(),
+    /// };
+    /// assert_eq!(command.to_args(), vec!["space", "--label", "dev work"]);
+    /// ```
+    LabelSpace {
+        label: String,
+    },
+    /// Sets the layout of the focused space (`space --layout`).
+    SetSpaceLayout {
+        layout: Layout,
+    },
+    /// Sets the padding of the focused space (`space --padding`).
+    ///
+    /// In [`RelAbs::Rel`] mode, negative values shrink the current padding.
+    ///
+    /// ```
+    /// let command = yabai::Command::SetSpacePadding {
+    ///     mode: yabai::RelAbs::Rel,
+    ///     top: -10.0,
+    ///     bottom: -10.0,
+    ///     left: 0.0,
+    ///     right: 0.0,
+    /// };
+    /// assert_eq!(command.to_string(), "space --padding rel:-10:-10:0:0");
+    /// ```
+    SetSpacePadding {
+        mode: RelAbs,
+        top: f32,
+        bottom: f32,
+        left: f32,
+        right: f32,
+    },
+    /// Sets the gap between windows on the focused space (`space --gap`).
+    ///
+    /// In [`RelAbs::Rel`] mode, a negative amount shrinks the current gap.
+    SetSpaceGap {
+        mode: RelAbs,
+        amount: f32,
+    },
+    /// Focuses a display (`display --focus`).
+    FocusDisplay {
+        display: DisplaySelector,
+    },
+    /// Moves the focused window to the display with the given index (`window --display`).
+    MoveWindowToDisplay {
+        display: DisplayIndex,
+    },
+    /// Toggles a property of the focused window (`window --toggle`).
+    ///
+    /// Toggles a yabai version doesn't support, such as `WindowToggle::Border` on yabai 6, are
+    /// returned as a `YabaiError::CommandError`.
+    ToggleWindow {
+        toggle: WindowToggle,
+    },
+    /// Closes a window, or the focused window if no selector is given (`window --close`).
+    CloseWindow {
+        window: Option<WindowSelector>,
+    },
+    /// Minimizes a window, or the focused window if no selector is given (`window --minimize`).
+    MinimizeWindow {
+        window: Option<WindowSelector>,
+    },
+    /// Restores a minimized window (`window --deminimize`).
+    ///
+    /// yabai requires a selector, since a minimized window can't be focused.
+    DeminimizeWindow {
+        window: WindowSelector,
+    },
+    /// Stacks the focused window onto another window (`window --stack`).
+    ///
+    /// Stacking onto the window to the east:
+    ///
+    /// ```
+    /// let command = yabai::Command::StackWindow {
+    ///     window: yabai::WindowSelector::Direction {
+    ///         direction: yabai::Direction::East,
+    ///     },
+    /// };
+    /// assert_eq!(command.to_string(), "window --stack east");
+    /// ```
+    StackWindow {
+        window: WindowSelector,
+    },
+    /// Adds a signal that runs a shell command when an event occurs (`signal --add`).
+    ///
+    /// The action is sent as a single argument, so it may contain arbitrary shell.
+    ///
+    /// ```
+    /// let command = yabai::Command::AddSignal {
+    ///     event: yabai::Event::WindowFocused,
+    ///     action: "echo focused $YABAI_WINDOW_ID".to_string(),
+    ///     label: Some("log-focus".to_string()),
+    /// };
+    /// assert_eq!(
+    ///     command.to_args(),
+    ///     vec![
+    ///         "signal",
+    ///         "--add",
+    ///         "event=window_focused",
+    ///         "action=echo focused $YABAI_WINDOW_ID",
+    ///         "label=log-focus",
+    ///     ]
+    /// );
+    /// ```
+    AddSignal {
+        event: Event,
+        action: String,
+        label: Option<String>,
+    },
+    /// Removes the signal with the given label or index (`signal --remove`).
+    RemoveSignal {
+        label: String,
+    },
+    /// Adds a window rule (`rule --add`).
+    AddRule {
+        rule: Rule,
+    },
+    /// Removes the rule with the given label or index (`rule --remove`).
+    RemoveRule {
+        rule: String,
+    },
+    /// Sets a config setting, for a single space if `space` is given (`config`).
+    ///
+    /// The value is sent as a single argument. With a space this sends the same message as
+    /// `Command::SetSpaceConfig`, which it parses back into with `Command::from_message`.
+    SetConfig {
+        key: ConfigKey,
+        value: String,
+        space: Option<SpaceSelector>,
+    },
+    /// Adjusts the split ratio of the focused window's node (`window --ratio`).
+    ///
+    /// Relative values may be negative, and absolute values must be between 0.0 and 1.0.
+    SetWindowRatio {
+        mode: RelAbs,
+        value: f32,
+    },
+    /// Sets the stacking layer of the focused window (`window --layer`).
+    ///
+    /// Example, keeping the focused window above other windows:
+    ///
+    /// ```no_run
+    /// use yabai::{Command, Layer};
+    ///
+    /// yabai::send_command(&Command::SetWindowLayer { layer: Layer::Above })?;
+    /// # Ok::<(), yabai::YabaiError>(())
+    /// ```
+    SetWindowLayer {
+        layer: Layer,
+    },
+    /// Assigns the focused window to the named scratchpad, or removes it from its scratchpad
+    /// if the name is empty (`window --scratchpad`).
+    SetScratchpad {
+        name: String,
+    },
+    /// Shows or hides the window assigned to the named scratchpad (`window --toggle`).
+    ///
+    /// Scratchpad names are arbitrary, so these are toggled separately from `WindowToggle`.
+    /// The name is sent as a single argument:
+    ///
+    /// ```
+    /// let command = yabai::Command::ToggleScratchpad {
+    ///     name: "music player".to_string(),
+    /// };
+    ///
+    /// assert_eq!(command.to_args(), vec!["window", "--toggle", "music player"]);
+    /// ```
+    ToggleScratchpad {
+        name: String,
+    },
+    /// Toggles a property of the focused space (`space --toggle`).
+    ///
+    /// Toggles a yabai version doesn't support are returned as a `YabaiError::CommandError`.
+    ToggleSpace {
+        toggle: SpaceToggle,
+    },
+    /// Sets a config setting for a single space (`config --space`).
+    ///
+    /// The value is sent as a single argument:
+    ///
+    /// ```
+    /// let command = yabai::Command::SetSpaceConfig {
+    ///     space: yabai::SpaceSelector::Label {
+    ///         label: "code".to_string(),
+    ///     },
+    ///     key: yabai::ConfigKey::Layout,
+    ///     value: "bsp".to_string(),
+    /// };
+    ///
+    /// assert_eq!(
+    ///     command.to_args(),
+    ///     vec!["config", "--space", "code", "layout", "bsp"]
+    /// );
+    /// assert_eq!(yabai::Command::from_message(&command.to_string())?, command);
+    /// # Ok::<(), yabai::YabaiError>(())
+    /// ```
+    SetSpaceConfig {
+        space: SpaceSelector,
+        key: ConfigKey,
+        value: String,
+    },
+    /// Moves the focused space to the selected display (`space --display`).
+    ///
+    /// yabai refuses to move the last space on a display, which is returned as a
+    /// `YabaiError::CommandError`.
+    MoveSpaceToDisplay {
+        display: DisplaySelector,
+    },
+    /// Queries yabai for information about the selected kind of entity (`query`).
+    ///
+    /// Unlike other commands, yabai responds with JSON. Use `yabai::send_query` to deserialize
+    /// the response into a `QueryResult`.
+    Query {
+        kind: QueryKind,
+    },
+    /// Raises a window above other windows without focusing it, or the focused window if no
+    /// selector is given (`window --raise`).
+    ///
+    /// Unlike `Command::FocusWindow`, keyboard focus stays on the focused window, which makes
+    /// this useful for overlays:
+    ///
+    /// ```no_run
+    /// # #[cfg(feature = "serde")]
+    /// # {
+    /// use yabai::{Command, WindowSelector};
+    ///
+    /// let windows = yabai::query_windows()?.by_app("Activity Monitor");
+    ///
+    /// if let Some(window) = windows.first() {
+    ///     yabai::send_command(&Command::RaiseWindow {
+    ///         window: Some(WindowSelector::Id { id: window.id }),
+    ///     })?;
+    /// }
+    /// # }
+    /// # Ok::<(), yabai::YabaiError>(())
+    /// ```
+    RaiseWindow {
+        window: Option<WindowSelector>,
+    },
+    /// Sends an arbitrary message, split on spaces in the same way as `yabai::send`.
+    ///
+    /// An escape hatch for yabai messages that don't have a typed variant yet.
+    Custom {
+        message: String,
+    },
+}
+
+/// Builds a list of message arguments from values implementing `Display`.
+macro_rules! args {
+    ($($arg:expr),* $(,)?) => {
+        vec![$($arg.to_string()),*]
+    };
+}
+
+impl Command {
+    /// Returns the arguments of the message this command sends to yabai.
+    ///
+    /// ```
+    /// let command = yabai::Command::FocusWindowDirection {
+    ///     direction: yabai::Direction::West,
+    /// };
+    /// assert_eq!(command.to_args(), vec!["window", "--focus", "west"]);
+    /// ```
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            Command::FocusSpace { option } => args!["space", "--focus", option],
+            Command::RotateSpace { rotation } => args!["space", "--rotate", rotation],
+            Command::BalanceSpace {} => args!["space", "--balance"],
+            Command::MirrorSpace { axis } => args!["space", "--mirror", axis],
+            Command::EqualizeSpace {} => args!["space", "--equalize"],
+            Command::MoveActiveWindowToSpace { space } => args!["window", "--space", space],
+            Command::FocusWindow { window } => args!["window", "--focus", window],
+            Command::FocusWindowDirection { direction } => args!["window", "--focus", direction],
+            Command::SwapWindowDirection { direction } => args!["window", "--swap", direction],
+            Command::WarpWindowDirection { direction } => args!["window", "--warp", direction],
+            Command::SwapWindow { window } => args!["window", "--swap", window],
+            Command::WarpWindow { window } => args!["window", "--warp", window],
+            Command::ToggleWindowFloating {} => args!["window", "--toggle", WindowToggle::Float],
+            Command::ToggleZoomFullscreen {} => {
+                args!["window", "--toggle", WindowToggle::ZoomFullscreen]
+            }
+            Command::GridWindow {
+                rows,
+                cols,
+                start_x,
+                start_y,
+                width,
+                height,
+            } => {
+                let grid = Grid {
+                    rows: *rows,
+                    cols: *cols,
+                    start_x: *start_x,
+                    start_y: *start_y,
+                    width: *width,
+                    height: *height,
+                };
+
+                args!["window", "--grid", grid]
+            }
+            Command::MoveWindow { position } => args!["window", "--move", position],
+            Command::ResizeWindow { handle, dx, dy } => {
+                args!["window", "--resize", format!("{handle}:{dx}:{dy}")]
+            }
+            Command::SetWindowOpacity { opacity } => args!["window", "--opacity", opacity],
+            Command::SetSelectedWindowOpacity { window, opacity } => {
+                args!["window", window, "--opacity", opacity]
+            }
+            Command::InsertWindow { direction } => args!["window", "--insert", direction],
+            Command::CreateSpace {} => args!["space", "--create"],
+            Command::CreateSpaceOnDisplay { display } => args!["space", "--create", display],
+            Command::DestroySpace { space } => args!["space", "--destroy", space],
+            Command::MoveSpace { space } => args!["space", "--move", space],
+            Command::MoveSelectedSpace { space, to } => args!["space", space, "--move", to],
+            Command::SwapSpace { space } => args!["space", "--swap", space],
+            Command::LabelSpace { label } if label.is_empty() => args!["space", "--label"],
+            Command::LabelSpace { label } => args!["space", "--label", label],
+            Command::SetSpaceLayout { layout } => args!["space", "--layout", layout],
+            Command::SetSpacePadding {
+                mode,
+                top,
+                bottom,
+                left,
+                right,
+            } => args![
+                "space",
+                "--padding",
+                format!("{mode}:{top}:{bottom}:{left}:{right}")
+            ],
+            Command::SetSpaceGap { mode, amount } => {
+                args!["space", "--gap", format!("{mode}:{amount}")]
+            }
+            Command::FocusDisplay { display } => args!["display", "--focus", display],
+            Command::MoveWindowToDisplay { display } => args!["window", "--display", display],
+            Command::ToggleWindow { toggle } => args!["window", "--toggle", toggle],
+            Command::CloseWindow { window: None } => args!["window", "--close"],
+            Command::CloseWindow {
+                window: Some(window),
+            } => args!["window", "--close", window],
+            Command::MinimizeWindow { window: None } => args!["window", "--minimize"],
+            Command::MinimizeWindow {
+                window: Some(window),
+            } => args!["window", "--minimize", window],
+            Command::DeminimizeWindow { window } => args!["window", "--deminimize", window],
+            Command::StackWindow { window } => args!["window", "--stack", window],
+            Command::AddSignal {
+                event,
+                action,
+                label,
+            } => {
+                let mut args = args![
+                    "signal",
+                    "--add",
+                    format!("event={event}"),
+                    format!("action={action}")
+                ];
+
+                if let Some(label) = label {
+                    args.push(format!("label={label}"));
+                }
+
+                args
+            }
+            Command::RemoveSignal { label } => args!["signal", "--remove", label],
+            Command::AddRule { rule } => {
+                let mut args = args!["rule", "--add"];
+                args.extend(rule.to_args());
+                args
+            }
+            Command::RemoveRule { rule } => args!["rule", "--remove", rule],
+            Command::SetConfig {
+                key,
+                value,
+                space: None,
+            } => args!["config", key, value],
+            Command::SetConfig {
+                key,
+                value,
+                space: Some(space),
+            }
+            | Command::SetSpaceConfig { space, key, value } => {
+                args!["config", "--space", space, key, value]
+            }
+            Command::SetWindowRatio { mode, value } => {
+                args!["window", "--ratio", format!("{mode}:{value}")]
+            }
+            Command::SetWindowLayer { layer } => args!["window", "--layer", layer],
+            Command::SetScratchpad { name } => args!["window", "--scratchpad", name],
+            Command::ToggleScratchpad { name } => args!["window", "--toggle", name],
+            Command::ToggleSpace { toggle } => args!["space", "--toggle", toggle],
+            Command::MoveSpaceToDisplay { display } => args!["space", "--display", display],
+            Command::Query { kind } => args!["query", format!("--{}", kind)],
+            Command::RaiseWindow { window: None } => args!["window", "--raise"],
+            Command::RaiseWindow {
+                window: Some(window),
+            } => args!["window", "--raise", window],
+            Command::Custom { message } => split_message(message)
+                .into_iter()
+                .map(String::from)
+                .collect(),
+        }
+    }
+
+    /// Checks that the command's arguments are valid before it is sent to yabai.
+    pub fn validate(&self) -> Result<(), YabaiError> {
+        match self {
+            Command::GridWindow {
+                rows,
+                cols,
+                start_x,
+                start_y,
+                width,
+                height,
+            } => Grid::new(*rows, *cols, *start_x, *start_y, *width, *height).map(|_| ()),
+            Command::SetWindowOpacity { opacity }
+            | Command::SetSelectedWindowOpacity { opacity, .. }
+                if !(0.0..=1.0).contains(opacity) =>
+            {
+                Err(YabaiError::InvalidArgument(format!(
+                    "window opacity must be between 0.0 and 1.0, got {opacity}"
+                )))
+            }
+            Command::SetWindowRatio {
+                mode: RelAbs::Abs,
+                value,
+            } if !(*value > 0.0 && *value < 1.0) => Err(YabaiError::InvalidArgument(format!(
+                "absolute window ratio must be between 0.0 and 1.0, got {value}"
+            ))),
+            Command::AddRule {
+                rule:
+                    Rule {
+                        opacity: Some(opacity),
+                        ..
+                    },
+            } if !(0.0..=1.0).contains(opacity) => Err(YabaiError::InvalidArgument(format!(
+                "rule opacity must be between 0.0 and 1.0, got {opacity}"
+            ))),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Formats the command as the space-separated message sent to yabai.
+///
+/// ```
+/// assert_eq!(yabai::Command::BalanceSpace {}.to_string(), "space --balance");
+/// ```
+impl fmt::Display for Command {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_args().join(" "))
+    }
+}
+
+/// A **struct** representing a placement on a virtual grid, as used by the `window --grid` command.
+///
+/// Formats as `rows:cols:start-x:start-y:width:height`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "python", pyclass)]
+pub struct Grid {
+    pub rows: u32,
+    pub cols: u32,
+    pub start_x: u32,
+    pub start_y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Grid {
+    /// Creates a new grid placement, checking that the placed cell range fits within the grid.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// // The left half of a 1x2 grid.
+    /// let grid = yabai::Grid::new(1, 2, 0, 0, 1, 1)?;
+    /// assert_eq!(grid.to_string(), "1:2:0:0:1:1");
+    ///
+    /// // Two columns starting at column 1 don't fit in a 2 column grid.
+    /// assert!(yabai::Grid::new(1, 2, 1, 0, 2, 1).is_err());
+    /// # Ok::<(), yabai::YabaiError>(())
+    /// ```
+    pub fn new(
+        rows: u32,
+        cols: u32,
+        start_x: u32,
+        start_y: u32,
+        width: u32,
+        height: u32,
+    ) -> Result<Grid, YabaiError> {
+        if rows == 0 || cols == 0 {
+            return Err(YabaiError::InvalidArgument(format!(
+                "grid must have at least one row and column, got {rows}x{cols}"
+            )));
+        }
+
+        if width == 0 || height == 0 {
+            return Err(YabaiError::InvalidArgument(format!(
+                "grid placement must be at least one cell, got {width}x{height}"
+            )));
+        }
+
+        if width > cols || start_x > cols - width {
+            return Err(YabaiError::InvalidArgument(format!(
+                "grid placement exceeds columns: {start_x} + {width} > {cols}"
+            )));
+        }
+
+        if height > rows || start_y > rows - height {
+            return Err(YabaiError::InvalidArgument(format!(
+                "grid placement exceeds rows: {start_y} + {height} > {rows}"
+            )));
+        }
+
+        Ok(Grid {
+            rows,
+            cols,
+            start_x,
+            start_y,
+            width,
+            height,
+        })
+    }
+}
+
+impl fmt::Display for Grid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}:{}:{}:{}:{}",
+            self.rows, self.cols, self.start_x, self.start_y, self.width, self.height
+        )
+    }
+}
+
+/// Parses a grid from the `rows:cols:start_x:start_y:width:height` form, validating it with
+/// `Grid::new`.
+impl FromStr for Grid {
+    type Err = YabaiError;
+
+    fn from_str(token: &str) -> Result<Self, Self::Err> {
+        let values = token
+            .split(':')
+            .map(str::parse)
+            .collect::<Result<Vec<u32>, _>>()
+            .map_err(|_| YabaiError::InvalidArgument(format!("invalid grid {token:?}")))?;
+
+        match values[..] {
+            [rows, cols, start_x, start_y, width, height] => {
+                Grid::new(rows, cols, start_x, start_y, width, height)
+            }
+            _ => Err(YabaiError::InvalidArgument(format!(
+                "invalid grid {token:?}"
+            ))),
+        }
+    }
+}
+
+impl From<Grid> for Command {
+    fn from(grid: Grid) -> Self {
+        Command::GridWindow {
+            rows: grid.rows,
+            cols: grid.cols,
+            start_x: grid.start_x,
+            start_y: grid.start_y,
+            width: grid.width,
+            height: grid.height,
+        }
+    }
 }
 
 /// An **enum** representing the options passed to the `space --focus` command.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Display)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Display)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "python", pyclass)]
 pub enum FocusSpaceOption {
     #[strum(serialize = "next")]
@@ -37,13 +777,162 @@ pub enum FocusSpaceOption {
     Last {},
     #[strum(serialize = "recent")]
     Recent {},
-    Space {
-        space: u32,
-    },
+    #[strum(serialize = "mouse")]
+    Mouse {},
+    #[strum(to_string = "{space}")]
+    Space { space: SpaceIndex },
+    /// A space label. Sent as a single argument, so it may contain spaces.
+    ///
+    /// ```
+    /// use yabai::{Command, FocusSpaceOption};
+    ///
+    /// let command = Command::FocusSpace {
+    ///     option: FocusSpaceOption::Label {
+    ///         label: "team comms".to_string(),
+    ///     },
+    /// };
+    /// assert_eq!(command.to_args(), ["space", "--focus", "team comms"]);
+    ///
+    /// let mut bytes = Vec::new();
+    /// yabai::protocol::write_args(&mut bytes, &command.to_args())?;
+    /// assert_eq!(&bytes[4..], b"space\0--focus\0team comms\0\0");
+    /// # Ok::<(), yabai::YabaiError>(())
+    /// ```
+    #[strum(to_string = "{label}")]
+    Label { label: String },
+}
+
+/// Parses a `space --focus` option, treating numbers as space indexes and any other token as a
+/// label.
+impl FromStr for FocusSpaceOption {
+    type Err = YabaiError;
+
+    fn from_str(token: &str) -> Result<Self, Self::Err> {
+        let option = match token {
+            "next" => FocusSpaceOption::Next {},
+            "prev" => FocusSpaceOption::Prev {},
+            "first" => FocusSpaceOption::First {},
+            "last" => FocusSpaceOption::Last {},
+            "recent" => FocusSpaceOption::Recent {},
+            "mouse" => FocusSpaceOption::Mouse {},
+            _ => match token.parse::<u32>() {
+                Ok(index) => FocusSpaceOption::Space {
+                    space: SpaceIndex(index),
+                },
+                Err(_) => FocusSpaceOption::Label {
+                    label: token.to_string(),
+                },
+            },
+        };
+
+        Ok(option)
+    }
+}
+
+/// Focuses the space a selector picks.
+///
+/// ```
+/// use yabai::{FocusSpaceOption, SpaceIndex, SpaceSelector};
+///
+/// let option = FocusSpaceOption::from(SpaceSelector::Index { index: SpaceIndex(3) });
+/// assert_eq!(option, FocusSpaceOption::Space { space: SpaceIndex(3) });
+/// ```
+impl From<SpaceSelector> for FocusSpaceOption {
+    fn from(selector: SpaceSelector) -> Self {
+        match selector {
+            SpaceSelector::Prev {} => FocusSpaceOption::Prev {},
+            SpaceSelector::Next {} => FocusSpaceOption::Next {},
+            SpaceSelector::First {} => FocusSpaceOption::First {},
+            SpaceSelector::Last {} => FocusSpaceOption::Last {},
+            SpaceSelector::Recent {} => FocusSpaceOption::Recent {},
+            SpaceSelector::Mouse {} => FocusSpaceOption::Mouse {},
+            SpaceSelector::Index { index } => FocusSpaceOption::Space { space: index },
+            SpaceSelector::Label { label } => FocusSpaceOption::Label { label },
+        }
+    }
+}
+
+/// An **enum** representing the position passed to the `window --move` command.
+///
+/// Relative deltas may be negative to move a window left or up.
+///
+/// ```
+/// let position = yabai::MovePosition::Relative { dx: -20.0, dy: 0.0 };
+/// assert_eq!(position.to_string(), "rel:-20:0");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Display)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "python", pyclass)]
+pub enum MovePosition {
+    #[strum(to_string = "abs:{x}:{y}")]
+    Absolute { x: f32, y: f32 },
+    #[strum(to_string = "rel:{dx}:{dy}")]
+    Relative { dx: f32, dy: f32 },
+}
+
+/// Parses a position from the `abs:x:y` or `rel:dx:dy` form.
+impl FromStr for MovePosition {
+    type Err = YabaiError;
+
+    fn from_str(token: &str) -> Result<Self, Self::Err> {
+        let invalid = || YabaiError::InvalidArgument(format!("invalid position {token:?}"));
+
+        let mut parts = token.split(':');
+        let (mode, x, y) = match (parts.next(), parts.next(), parts.next(), parts.next()) {
+            (Some(mode), Some(x), Some(y), None) => (mode, x, y),
+            _ => return Err(invalid()),
+        };
+
+        let x = x.parse::<f32>().map_err(|_| invalid())?;
+        let y = y.parse::<f32>().map_err(|_| invalid())?;
+
+        match mode {
+            "abs" => Ok(MovePosition::Absolute { x, y }),
+            "rel" => Ok(MovePosition::Relative { dx: x, dy: y }),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+/// An **enum** representing the edge or corner dragged by the `window --resize` command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "python", pyclass)]
+pub enum ResizeHandle {
+    #[strum(serialize = "top")]
+    Top,
+    #[strum(serialize = "bottom")]
+    Bottom,
+    #[strum(serialize = "left")]
+    Left,
+    #[strum(serialize = "right")]
+    Right,
+    #[strum(serialize = "top_left")]
+    TopLeft,
+    #[strum(serialize = "top_right")]
+    TopRight,
+    #[strum(serialize = "bottom_left")]
+    BottomLeft,
+    #[strum(serialize = "bottom_right")]
+    BottomRight,
+    #[strum(serialize = "abs")]
+    Abs,
+}
+
+/// An **enum** representing the options passed to the `space --mirror` command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "python", pyclass)]
+pub enum MirrorAxis {
+    #[strum(serialize = "x-axis")]
+    XAxis,
+    #[strum(serialize = "y-axis")]
+    YAxis,
 }
 
 /// An **enum** representing the options passed to the `space --rotate` command.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Display)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "python", pyclass)]
 pub enum SpaceRotation {
     #[strum(serialize = "90")]
@@ -55,7 +944,12 @@ pub enum SpaceRotation {
 }
 
 /// An **enum** representing a cardinal direction.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Display)]
+///
+/// Used wherever yabai accepts a direction for focusing, swapping, or warping windows, and for
+/// selecting displays. Only `window --insert` also accepts `stack`, so it takes an
+/// [`InsertDirection`] instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "python", pyclass)]
 pub enum Direction {
     #[strum(serialize = "north")]
@@ -68,73 +962,763 @@ pub enum Direction {
     West,
 }
 
+/// An **enum** representing the direction spaces are cycled through by
+/// `yabai::focus_space_cyclic`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "python", pyclass)]
+pub enum CycleDirection {
+    #[strum(serialize = "next")]
+    Next,
+    #[strum(serialize = "prev")]
+    Prev,
+}
+
+/// An **enum** representing the options passed to the `window --insert` command.
+///
+/// A superset of [`Direction`] that also allows stacking onto the focused window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "python", pyclass)]
+pub enum InsertDirection {
+    #[strum(serialize = "north")]
+    North,
+    #[strum(serialize = "south")]
+    South,
+    #[strum(serialize = "east")]
+    East,
+    #[strum(serialize = "west")]
+    West,
+    #[strum(serialize = "stack")]
+    Stack,
+}
+
+impl From<Direction> for InsertDirection {
+    fn from(direction: Direction) -> Self {
+        match direction {
+            Direction::North => InsertDirection::North,
+            Direction::South => InsertDirection::South,
+            Direction::East => InsertDirection::East,
+            Direction::West => InsertDirection::West,
+        }
+    }
+}
+
+/// Converts an insert direction back into a cardinal direction, failing for `Stack`.
+///
+/// ```
+/// use yabai::{Direction, InsertDirection};
+///
+/// assert_eq!(Direction::try_from(InsertDirection::East).ok(), Some(Direction::East));
+/// assert!(Direction::try_from(InsertDirection::Stack).is_err());
+/// ```
+impl TryFrom<InsertDirection> for Direction {
+    type Error = YabaiError;
+
+    fn try_from(direction: InsertDirection) -> Result<Self, Self::Error> {
+        match direction {
+            InsertDirection::North => Ok(Direction::North),
+            InsertDirection::South => Ok(Direction::South),
+            InsertDirection::East => Ok(Direction::East),
+            InsertDirection::West => Ok(Direction::West),
+            InsertDirection::Stack => Err(YabaiError::InvalidArgument(
+                "stack is not a cardinal direction".to_string(),
+            )),
+        }
+    }
+}
+
+/// An **enum** representing the stacking layer of a window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "python", pyclass)]
+pub enum Layer {
+    #[strum(serialize = "below")]
+    Below,
+    #[strum(serialize = "normal")]
+    Normal,
+    #[strum(serialize = "above")]
+    Above,
+}
+
+/// An **enum** representing the layout of a space.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "python", pyclass)]
+pub enum Layout {
+    #[strum(serialize = "bsp")]
+    Bsp,
+    #[strum(serialize = "stack")]
+    Stack,
+    #[strum(serialize = "float")]
+    Float,
+}
+
+/// An **enum** representing whether a value is relative to the current value or absolute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "python", pyclass)]
+pub enum RelAbs {
+    #[strum(serialize = "rel")]
+    Rel,
+    #[strum(serialize = "abs")]
+    Abs,
+}
+
+/// An **enum** representing the window properties toggled by the `window --toggle` command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "python", pyclass)]
+pub enum WindowToggle {
+    #[strum(serialize = "float")]
+    Float,
+    #[strum(serialize = "sticky")]
+    Sticky,
+    #[strum(serialize = "topmost")]
+    Topmost,
+    #[strum(serialize = "pip")]
+    Pip,
+    #[strum(serialize = "shadow")]
+    Shadow,
+    /// Toggles the window's border, which yabai 6 removed along with the border settings.
+    #[strum(serialize = "border")]
+    Border,
+    #[strum(serialize = "split")]
+    Split,
+    /// Shows the windows of the focused window's app in mission control's exposé.
+    #[strum(serialize = "expose")]
+    Expose,
+    #[strum(serialize = "zoom-parent")]
+    ZoomParent,
+    #[strum(serialize = "zoom-fullscreen")]
+    ZoomFullscreen,
+    #[strum(serialize = "native-fullscreen")]
+    NativeFullscreen,
+}
+
+/// An **enum** representing the kinds of entity yabai can be queried for with `query`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "python", pyclass)]
+pub enum QueryKind {
+    #[strum(serialize = "displays")]
+    Displays,
+    #[strum(serialize = "spaces")]
+    Spaces,
+    #[strum(serialize = "windows")]
+    Windows,
+}
+
+/// An **enum** representing the space properties toggled by the `space --toggle` command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "python", pyclass)]
+pub enum SpaceToggle {
+    #[strum(serialize = "padding")]
+    Padding,
+    #[strum(serialize = "gap")]
+    Gap,
+    /// Opens or closes mission control.
+    #[strum(serialize = "mission-control")]
+    MissionControl,
+    /// Moves all windows aside to show the desktop, or brings them back.
+    #[strum(serialize = "show-desktop")]
+    ShowDesktop,
+}
+
+/// An **enum** representing the layout reported for a space by yabai.
+///
+/// Unlike [`Layout`], this has an `Other` case so that layouts added by future versions of
+/// yabai can still be represented.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Display)]
+pub enum SpaceLayout {
+    #[strum(serialize = "bsp")]
+    Bsp,
+    #[strum(serialize = "stack")]
+    Stack,
+    #[strum(serialize = "float")]
+    Float,
+    #[strum(to_string = "{0}")]
+    Other(String),
+}
+
+impl From<&str> for SpaceLayout {
+    fn from(layout: &str) -> Self {
+        match layout {
+            "bsp" => SpaceLayout::Bsp,
+            "stack" => SpaceLayout::Stack,
+            "float" => SpaceLayout::Float,
+            other => SpaceLayout::Other(other.to_string()),
+        }
+    }
+}
+
+impl From<Layout> for SpaceLayout {
+    fn from(layout: Layout) -> Self {
+        match layout {
+            Layout::Bsp => SpaceLayout::Bsp,
+            Layout::Stack => SpaceLayout::Stack,
+            Layout::Float => SpaceLayout::Float,
+        }
+    }
+}
+
+/// An **enum** representing the split reported for a window by yabai, which
+/// `window --toggle split` flips between vertical and horizontal.
+///
+/// Split types not known to this crate are represented by the `Other` case.
+///
+/// ```
+/// use yabai::SplitType;
+///
+/// assert_eq!(SplitType::from("vertical"), SplitType::Vertical);
+/// assert_eq!(SplitType::from("diagonal"), SplitType::Other("diagonal".to_string()));
+/// assert_eq!(SplitType::Horizontal.to_string(), "horizontal");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Display)]
+pub enum SplitType {
+    #[strum(serialize = "vertical")]
+    Vertical,
+    #[strum(serialize = "horizontal")]
+    Horizontal,
+    #[strum(serialize = "none")]
+    None,
+    #[strum(to_string = "{0}")]
+    Other(String),
+}
+
+impl From<&str> for SplitType {
+    fn from(split: &str) -> Self {
+        match split {
+            "vertical" => SplitType::Vertical,
+            "horizontal" => SplitType::Horizontal,
+            "none" => SplitType::None,
+            other => SplitType::Other(other.to_string()),
+        }
+    }
+}
+
+/// An **enum** representing the accessibility role reported for a window by yabai.
+///
+/// Roles not known to this crate are represented by the `Other` case.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Display)]
+pub enum Role {
+    #[strum(serialize = "AXWindow")]
+    Window,
+    #[strum(serialize = "AXSheet")]
+    Sheet,
+    #[strum(serialize = "AXDrawer")]
+    Drawer,
+    #[strum(serialize = "AXPopover")]
+    Popover,
+    #[strum(to_string = "{0}")]
+    Other(String),
+}
+
+impl From<&str> for Role {
+    fn from(role: &str) -> Self {
+        match role {
+            "AXWindow" => Role::Window,
+            "AXSheet" => Role::Sheet,
+            "AXDrawer" => Role::Drawer,
+            "AXPopover" => Role::Popover,
+            other => Role::Other(other.to_string()),
+        }
+    }
+}
+
+/// An **enum** representing the accessibility subrole reported for a window by yabai.
+///
+/// Subroles not known to this crate are represented by the `Other` case.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Display)]
+pub enum Subrole {
+    #[strum(serialize = "AXStandardWindow")]
+    StandardWindow,
+    #[strum(serialize = "AXDialog")]
+    Dialog,
+    #[strum(serialize = "AXSystemDialog")]
+    SystemDialog,
+    #[strum(serialize = "AXFloatingWindow")]
+    FloatingWindow,
+    #[strum(serialize = "AXSystemFloatingWindow")]
+    SystemFloatingWindow,
+    #[strum(serialize = "AXUnknown")]
+    Unknown,
+    #[strum(to_string = "{0}")]
+    Other(String),
+}
+
+impl From<&str> for Subrole {
+    fn from(subrole: &str) -> Self {
+        match subrole {
+            "AXStandardWindow" => Subrole::StandardWindow,
+            "AXDialog" => Subrole::Dialog,
+            "AXSystemDialog" => Subrole::SystemDialog,
+            "AXFloatingWindow" => Subrole::FloatingWindow,
+            "AXSystemFloatingWindow" => Subrole::SystemFloatingWindow,
+            "AXUnknown" => Subrole::Unknown,
+            other => Subrole::Other(other.to_string()),
+        }
+    }
+}
+
 /// Information about a mission control space.
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
-#[serde(rename_all = "kebab-case")]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
 #[cfg_attr(feature = "python", pyclass)]
 pub struct SpaceInfo {
     pub id: u32,
     pub uuid: String,
-    pub index: u32,
+    pub index: SpaceIndex,
     pub label: String,
     pub r#type: String,
-    pub display: u32,
-    pub windows: Vec<u32>,
-    pub first_window: u32,
-    pub last_window: u32,
+    pub display: DisplayIndex,
+    pub windows: Vec<WindowId>,
+    pub first_window: WindowId,
+    pub last_window: WindowId,
     pub has_focus: bool,
     pub is_visible: bool,
     pub is_native_fullscreen: bool,
 }
 
+impl SpaceInfo {
+    /// Returns the layout of the space, parsed from its `type` field.
+    pub fn layout(&self) -> SpaceLayout {
+        SpaceLayout::from(self.r#type.as_str())
+    }
+
+    /// Returns the position of the space among the spaces on its display, counting from 1,
+    /// along with the number of spaces on the display.
+    ///
+    /// Positions follow the space indexes in `all`, so they stay correct as spaces are created
+    /// and destroyed as long as `all` is up to date. Returns `None` if the space isn't in `all`,
+    /// matched by its id, such as when it comes from an older query:
+    ///
+    /// ```
+    /// # use yabai::{DisplayIndex, SpaceIndex, SpaceInfo, WindowId};
+    /// # let space = |index, display| SpaceInfo {
+    /// #     id: index, uuid: String::new(), index: SpaceIndex(index), label: String::new(),
+    /// #     r#type: "bsp".to_string(), display: DisplayIndex(display), windows: Vec::new(),
+    /// #     first_window: WindowId(0), last_window: WindowId(0), has_focus: false,
+    /// #     is_visible: false, is_native_fullscreen: false,
+    /// # };
+    /// let spaces = vec![space(1, 1), space(2, 1), space(3, 2), space(4, 1)];
+    ///
+    /// assert_eq!(spaces[1].position_on_display(&spaces), Some((2, 3)));
+    /// assert_eq!(spaces[2].position_on_display(&spaces), Some((1, 1)));
+    /// assert!(spaces[0].is_first_on_display(&spaces));
+    /// assert!(spaces[3].is_last_on_display(&spaces));
+    ///
+    /// let destroyed = space(5, 1);
+    /// assert_eq!(destroyed.position_on_display(&spaces), None);
+    /// assert!(!destroyed.is_last_on_display(&spaces));
+    /// ```
+    pub fn position_on_display(&self, all: &[SpaceInfo]) -> Option<(usize, usize)> {
+        let mut on_display = all
+            .iter()
+            .filter(|space| space.display == self.display)
+            .collect::<Vec<&SpaceInfo>>();
+
+        on_display.sort_by_key(|space| space.index);
+
+        let position = on_display.iter().position(|space| space.id == self.id)?;
+
+        Some((position + 1, on_display.len()))
+    }
+
+    /// Returns `true` if the space comes first among the spaces on its display in `all`.
+    ///
+    /// Returns `false` if the space isn't in `all`.
+    pub fn is_first_on_display(&self, all: &[SpaceInfo]) -> bool {
+        matches!(self.position_on_display(all), Some((1, _)))
+    }
+
+    /// Returns `true` if the space comes last among the spaces on its display in `all`.
+    ///
+    /// Returns `false` if the space isn't in `all`.
+    pub fn is_last_on_display(&self, all: &[SpaceInfo]) -> bool {
+        matches!(self.position_on_display(all), Some((position, total)) if position == total)
+    }
+
+    /// Orders spaces by display, then by index, for sorting with `sort_by`.
+    pub fn display_cmp(&self, other: &Self) -> Ordering {
+        (self.display, self.index).cmp(&(other.display, other.index))
+    }
+}
+
 /// Information about a display.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
 #[cfg_attr(feature = "python", pyclass)]
 pub struct DisplayInfo {
     pub id: u32,
     pub uuid: String,
-    pub index: u32,
+    pub index: DisplayIndex,
+    /// The label of the display, or `None` if the display is unlabeled.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, deserialize_with = "empty_string_as_none")
+    )]
+    pub label: Option<String>,
     pub frame: Frame,
-    pub spaces: Vec<u32>,
+    pub spaces: Vec<SpaceIndex>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub has_focus: bool,
+}
+
+impl DisplayInfo {
+    /// Returns the spaces on the display, in the order yabai lists them, by looking up each of
+    /// its space indexes in `all`.
+    ///
+    /// Spaces missing from `all` are skipped. To query the spaces on a display directly, use
+    /// `query_spaces_on_display`.
+    ///
+    /// ```no_run
+    /// # #[cfg(feature = "serde")]
+    /// # {
+    /// let spaces = yabai::query_spaces()?;
+    ///
+    /// for display in yabai::query_displays()? {
+    ///     let labels = display
+    ///         .resolve_spaces(&spaces)
+    ///         .iter()
+    ///         .map(|space| space.label.as_str())
+    ///         .collect::<Vec<&str>>();
+    ///
+    ///     println!("display {}: {:?}", display.index, labels);
+    /// }
+    /// # }
+    /// # Ok::<(), yabai::YabaiError>(())
+    /// ```
+    pub fn resolve_spaces<'a>(&self, all: &'a [SpaceInfo]) -> Vec<&'a SpaceInfo> {
+        self.spaces
+            .iter()
+            .filter_map(|index| all.iter().find(|space| space.index == *index))
+            .collect()
+    }
+}
+
+/// Deserializes an optional string, treating an empty string the same as a missing one.
+#[cfg(feature = "serde")]
+fn empty_string_as_none<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<String>, D::Error> {
+    let value = Option::<String>::deserialize(deserializer)?;
+
+    Ok(value.filter(|value| !value.is_empty()))
 }
 
 /// Information about a window.
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
-#[serde(rename_all = "kebab-case")]
+///
+/// Fields that are only reported by some versions of yabai are optional, so that windows
+/// from older versions still deserialize. Apart from the window's identity and position,
+/// missing fields fall back to their default values, and unknown fields are ignored.
+///
+/// ```
+/// # #[cfg(feature = "serde")]
+/// # {
+/// let json = r#"{
+///     "id": 4521,
+///     "pid": 712,
+///     "app": "Safari",
+///     "title": "GitHub",
+///     "scratchpad": "",
+///     "frame": { "x": 0.0000, "y": 25.0000, "w": 1512.0000, "h": 957.0000 },
+///     "role": "AXWindow",
+///     "subrole": "AXStandardWindow",
+///     "root-window": true,
+///     "display": 1,
+///     "space": 2,
+///     "level": 0,
+///     "sub-level": 0,
+///     "layer": "normal",
+///     "sub-layer": "normal",
+///     "opacity": 1.0000,
+///     "split-type": "none",
+///     "split-child": "none",
+///     "stack-index": 0,
+///     "can-move": true,
+///     "can-resize": true,
+///     "has-focus": true,
+///     "has-shadow": true,
+///     "has-parent-zoom": false,
+///     "has-fullscreen-zoom": false,
+///     "has-ax-reference": true,
+///     "is-native-fullscreen": false,
+///     "is-visible": true,
+///     "is-minimized": false,
+///     "is-hidden": false,
+///     "is-floating": false,
+///     "is-sticky": false,
+///     "is-grabbed": false
+/// }"#;
+///
+/// let window: yabai::WindowInfo = serde_json::from_str(json)?;
+/// assert_eq!(window.app, "Safari");
+/// assert_eq!(window.root_window, Some(true));
+///
+/// // A window reported by an older version of yabai, without most of the newer fields.
+/// let json = r#"{
+///     "id": 4521,
+///     "pid": 712,
+///     "app": "Safari",
+///     "title": "GitHub",
+///     "frame": { "x": 0.0, "y": 25.0, "w": 1512.0, "h": 957.0 },
+///     "display": 1,
+///     "space": 2,
+///     "has-focus": true
+/// }"#;
+///
+/// let window: yabai::WindowInfo = serde_json::from_str(json)?;
+/// assert!(window.has_focus);
+/// assert_eq!(window.has_ax_reference, None);
+/// # }
+/// # Ok::<(), yabai::YabaiError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
 #[cfg_attr(feature = "python", pyclass)]
 pub struct WindowInfo {
-    pub id: u32,
+    pub id: WindowId,
     pub pid: u32,
     pub app: String,
     pub title: String,
+    /// The name of the scratchpad the window is assigned to, or an empty string.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub scratchpad: String,
     pub frame: Frame,
+    #[cfg_attr(feature = "serde", serde(default))]
     pub role: String,
+    #[cfg_attr(feature = "serde", serde(default))]
     pub subrole: String,
-    pub display: u32,
-    pub space: u32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub root_window: Option<bool>,
+    pub display: DisplayIndex,
+    pub space: SpaceIndex,
+    #[cfg_attr(feature = "serde", serde(default))]
     pub level: i32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub sub_level: Option<i32>,
+    #[cfg_attr(feature = "serde", serde(default))]
     pub layer: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub sub_layer: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
     pub opacity: f32,
+    #[cfg_attr(feature = "serde", serde(default))]
     pub split_type: String,
+    #[cfg_attr(feature = "serde", serde(default))]
     pub split_child: String,
+    #[cfg_attr(feature = "serde", serde(default))]
     pub stack_index: u32,
+    #[cfg_attr(feature = "serde", serde(default))]
     pub can_move: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
     pub can_resize: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
     pub has_focus: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
     pub has_shadow: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
     pub has_parent_zoom: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
     pub has_fullscreen_zoom: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub has_ax_reference: Option<bool>,
+    #[cfg_attr(feature = "serde", serde(default))]
     pub is_native_fullscreen: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
     pub is_visible: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
     pub is_minimized: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
     pub is_hidden: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
     pub is_floating: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
     pub is_sticky: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
     pub is_grabbed: bool,
 }
 
+impl WindowInfo {
+    /// Returns how the window is split from its sibling, parsed from its `split_type` field.
+    pub fn split(&self) -> SplitType {
+        SplitType::from(self.split_type.as_str())
+    }
+
+    /// Returns the accessibility role of the window, parsed from its `role` field.
+    pub fn role(&self) -> Role {
+        Role::from(self.role.as_str())
+    }
+
+    /// Returns the accessibility subrole of the window, parsed from its `subrole` field.
+    pub fn subrole(&self) -> Subrole {
+        Subrole::from(self.subrole.as_str())
+    }
+
+    /// Returns `true` if the window is a standard window, rather than a dialog, panel or sheet.
+    ///
+    /// ```no_run
+    /// # #[cfg(feature = "serde")]
+    /// # {
+    /// let windows = yabai::query_windows()?.filter(yabai::WindowInfo::is_standard_window);
+    /// # }
+    /// # Ok::<(), yabai::YabaiError>(())
+    /// ```
+    pub fn is_standard_window(&self) -> bool {
+        self.role() == Role::Window && self.subrole() == Subrole::StandardWindow
+    }
+
+    /// Returns the display the window is on, matched by display index.
+    pub fn display_of<'a>(&self, displays: &'a [DisplayInfo]) -> Option<&'a DisplayInfo> {
+        displays
+            .iter()
+            .find(|display| display.index == self.display)
+    }
+
+    /// Returns `true` if the window is on the space with the given index.
+    pub fn is_on_space(&self, space: SpaceIndex) -> bool {
+        self.space == space
+    }
+
+    /// Orders windows by space, then top to bottom and left to right by their frame, for
+    /// sorting with `sort_by`.
+    ///
+    /// ```no_run
+    /// # #[cfg(feature = "serde")]
+    /// # {
+    /// let mut windows = yabai::query_windows()?;
+    /// windows.sort_by(yabai::WindowInfo::layout_cmp);
+    /// # }
+    /// # Ok::<(), yabai::YabaiError>(())
+    /// ```
+    pub fn layout_cmp(&self, other: &Self) -> Ordering {
+        self.space
+            .cmp(&other.space)
+            .then(self.frame.y.total_cmp(&other.frame.y))
+            .then(self.frame.x.total_cmp(&other.frame.x))
+    }
+}
+
+/// A list of windows returned by the window queries, such as `query_windows`, with combinators
+/// for the common filters.
+///
+/// `Windows` derefs to `[WindowInfo]`, so slice methods can be used directly.
+///
+/// ```no_run
+/// # #[cfg(feature = "serde")]
+/// # {
+/// use yabai::SpaceIndex;
+///
+/// let terminals = yabai::query_windows()?
+///     .on_space(SpaceIndex(1))
+///     .by_app("Terminal")
+///     .into_vec();
+/// # }
+/// # Ok::<(), yabai::YabaiError>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+#[repr(transparent)]
+pub struct Windows(pub Vec<WindowInfo>);
+
+impl Windows {
+    /// Keeps the windows on the space with the given index.
+    pub fn on_space(self, space: impl Into<SpaceIndex>) -> Self {
+        let space = space.into();
+        self.filter(|window| window.is_on_space(space))
+    }
+
+    /// Keeps the windows belonging to the app with the given name.
+    pub fn by_app(self, app: &str) -> Self {
+        self.filter(|window| window.app == app)
+    }
+
+    /// Keeps the windows belonging to the process with the given id.
+    pub fn by_pid(self, pid: u32) -> Self {
+        self.filter(|window| window.pid == pid)
+    }
+
+    /// Keeps the floating windows.
+    pub fn floating(self) -> Self {
+        self.filter(|window| window.is_floating)
+    }
+
+    /// Keeps the focused window.
+    pub fn focused(self) -> Self {
+        self.filter(|window| window.has_focus)
+    }
+
+    /// Keeps the windows matching the predicate.
+    pub fn filter(mut self, predicate: impl FnMut(&WindowInfo) -> bool) -> Self {
+        self.0.retain(predicate);
+        self
+    }
+
+    /// Returns the underlying list of windows.
+    pub fn into_vec(self) -> Vec<WindowInfo> {
+        self.0
+    }
+}
+
+impl Deref for Windows {
+    type Target = [WindowInfo];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for Windows {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl From<Vec<WindowInfo>> for Windows {
+    fn from(windows: Vec<WindowInfo>) -> Self {
+        Windows(windows)
+    }
+}
+
+impl From<Windows> for Vec<WindowInfo> {
+    fn from(windows: Windows) -> Self {
+        windows.0
+    }
+}
+
+impl IntoIterator for Windows {
+    type Item = WindowInfo;
+    type IntoIter = std::vec::IntoIter<WindowInfo>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Windows {
+    type Item = &'a WindowInfo;
+    type IntoIter = std::slice::Iter<'a, WindowInfo>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl FromIterator<WindowInfo> for Windows {
+    fn from_iter<I: IntoIterator<Item = WindowInfo>>(iter: I) -> Self {
+        Windows(iter.into_iter().collect())
+    }
+}
+
 /// A rectangle representing the position and size of a window or display.
-#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "python", pyclass)]
 pub struct Frame {
     pub x: f32,
@@ -142,3 +1726,126 @@ pub struct Frame {
     pub w: f32,
     pub h: f32,
 }
+
+impl Frame {
+    /// Creates a frame from its position and size.
+    pub fn from_xywh(x: f32, y: f32, w: f32, h: f32) -> Self {
+        Frame { x, y, w, h }
+    }
+
+    /// Returns the area of the frame.
+    pub fn area(&self) -> f32 {
+        self.w * self.h
+    }
+
+    /// Returns the center point of the frame as `(x, y)`.
+    pub fn center(&self) -> (f32, f32) {
+        (self.x + self.w / 2.0, self.y + self.h / 2.0)
+    }
+
+    /// Returns `true` if the point lies within the frame.
+    ///
+    /// The left and top edges are inclusive, while the right and bottom edges are exclusive,
+    /// so a point on the border between two adjacent frames is only contained by one of them.
+    ///
+    /// ```
+    /// let frame = yabai::Frame { x: 0.0, y: 0.0, w: 100.0, h: 50.0 };
+    /// assert!(frame.contains_point(0.0, 0.0));
+    /// assert!(frame.contains_point(99.5, 49.5));
+    /// assert!(!frame.contains_point(100.0, 25.0));
+    /// ```
+    pub fn contains_point(&self, x: f32, y: f32) -> bool {
+        x >= self.x && x < self.x + self.w && y >= self.y && y < self.y + self.h
+    }
+
+    /// Returns `true` if the two frames overlap.
+    ///
+    /// Frames that only share an edge do not intersect.
+    ///
+    /// ```
+    /// let left = yabai::Frame { x: 0.0, y: 0.0, w: 100.0, h: 100.0 };
+    /// let right = yabai::Frame { x: 100.0, y: 0.0, w: 100.0, h: 100.0 };
+    /// let middle = yabai::Frame { x: 50.0, y: 50.0, w: 100.0, h: 100.0 };
+    /// assert!(!left.intersects(&right));
+    /// assert!(left.intersects(&middle));
+    /// ```
+    pub fn intersects(&self, other: &Frame) -> bool {
+        self.x < other.x + other.w
+            && other.x < self.x + self.w
+            && self.y < other.y + other.h
+            && other.y < self.y + self.h
+    }
+
+    /// Returns the ratio of the frame's width to its height.
+    ///
+    /// Returns `0.0` for frames with no height, rather than dividing by zero.
+    ///
+    /// ```
+    /// let frame = yabai::Frame { x: 0.0, y: 0.0, w: 1920.0, h: 1080.0 };
+    /// assert_eq!(frame.aspect_ratio(), 16.0 / 9.0);
+    ///
+    /// let empty = yabai::Frame { x: 0.0, y: 0.0, w: 1920.0, h: 0.0 };
+    /// assert_eq!(empty.aspect_ratio(), 0.0);
+    /// ```
+    pub fn aspect_ratio(&self) -> f32 {
+        if self.h == 0.0 {
+            0.0
+        } else {
+            self.w / self.h
+        }
+    }
+}
+
+/// Formats the frame as `x y w h`.
+///
+/// ```
+/// let frame = yabai::Frame::from_xywh(0.0, 25.0, 1280.0, 775.5);
+/// assert_eq!(frame.to_string(), "0 25 1280 775.5");
+/// ```
+impl fmt::Display for Frame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} {} {}", self.x, self.y, self.w, self.h)
+    }
+}
+
+/// Converts the frame into `(x, y, w, h)`.
+///
+/// ```
+/// let frame = yabai::Frame::from_xywh(1.0, 2.0, 3.0, 4.0);
+/// let tuple: (f32, f32, f32, f32) = frame.into();
+/// assert_eq!(tuple, (1.0, 2.0, 3.0, 4.0));
+/// assert_eq!(yabai::Frame::from(tuple), frame);
+/// ```
+impl From<Frame> for (f32, f32, f32, f32) {
+    fn from(frame: Frame) -> Self {
+        (frame.x, frame.y, frame.w, frame.h)
+    }
+}
+
+/// Converts the frame into `[x, y, w, h]`.
+///
+/// ```
+/// let frame = yabai::Frame::from_xywh(1.0, 2.0, 3.0, 4.0);
+/// let array: [f32; 4] = frame.into();
+/// assert_eq!(array, [1.0, 2.0, 3.0, 4.0]);
+/// assert_eq!(yabai::Frame::from(array), frame);
+/// ```
+impl From<Frame> for [f32; 4] {
+    fn from(frame: Frame) -> Self {
+        [frame.x, frame.y, frame.w, frame.h]
+    }
+}
+
+/// Creates a frame from `(x, y, w, h)`.
+impl From<(f32, f32, f32, f32)> for Frame {
+    fn from((x, y, w, h): (f32, f32, f32, f32)) -> Self {
+        Frame::from_xywh(x, y, w, h)
+    }
+}
+
+/// Creates a frame from `[x, y, w, h]`.
+impl From<[f32; 4]> for Frame {
+    fn from([x, y, w, h]: [f32; 4]) -> Self {
+        Frame::from_xywh(x, y, w, h)
+    }
+}
@@ -0,0 +1,148 @@
+use crate::YabaiError;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+use strum_macros::{Display, EnumString};
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+/// An **enum** representing a yabai config setting.
+///
+/// See the [yabai documentation](https://github.com/koekeishiya/yabai/wiki/Commands#config) for
+/// the values each setting accepts. Settings from `Layout` to `WindowGap` can also be set for a
+/// single space.
+///
+/// The window border settings, from `WindowBorder` to `NormalWindowBorderColor`, were removed
+/// in yabai 6, which rejects them.
+///
+/// ```
+/// assert_eq!(yabai::ConfigKey::WindowGap.to_string(), "window_gap");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[strum(serialize_all = "snake_case")]
+#[cfg_attr(feature = "python", pyclass)]
+pub enum ConfigKey {
+    DebugOutput,
+    ExternalBar,
+    MenubarOpacity,
+    MouseFollowsFocus,
+    FocusFollowsMouse,
+    DisplayArrangementOrder,
+    WindowOriginDisplay,
+    WindowPlacement,
+    WindowZoomPersist,
+    WindowShadow,
+    WindowOpacity,
+    WindowOpacityDuration,
+    ActiveWindowOpacity,
+    NormalWindowOpacity,
+    WindowAnimationDuration,
+    WindowAnimationEasing,
+    WindowBorder,
+    WindowBorderWidth,
+    WindowBorderRadius,
+    WindowBorderBlur,
+    ActiveWindowBorderColor,
+    NormalWindowBorderColor,
+    InsertFeedbackColor,
+    SplitRatio,
+    SplitType,
+    AutoBalance,
+    MouseModifier,
+    MouseAction1,
+    MouseAction2,
+    MouseDropAction,
+    Layout,
+    TopPadding,
+    BottomPadding,
+    LeftPadding,
+    RightPadding,
+    WindowGap,
+}
+
+/// An **enum** representing the values of the `focus_follows_mouse` setting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "python", pyclass)]
+pub enum FocusFollowsMouse {
+    #[strum(serialize = "autofocus")]
+    Autofocus,
+    #[strum(serialize = "autoraise")]
+    Autoraise,
+    #[strum(serialize = "off")]
+    Off,
+}
+
+/// A color in yabai's `0xAARRGGBB` format, as used by the color settings.
+///
+/// Formats as the `0x` prefixed hex value yabai expects, and parses with or without the
+/// prefix:
+///
+/// ```
+/// use yabai::Color;
+///
+/// let color = Color::from_argb(0xff, 0x77, 0x57, 0x59);
+/// assert_eq!(color.to_string(), "0xff775759");
+/// assert_eq!("0xff775759".parse::<Color>()?, color);
+/// assert_eq!("FF775759".parse::<Color>()?, color);
+/// assert!("0xfff".parse::<Color>().is_err());
+/// # Ok::<(), yabai::YabaiError>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+#[repr(transparent)]
+#[cfg_attr(feature = "python", pyclass)]
+pub struct Color(pub u32);
+
+impl Color {
+    /// Creates a color from its alpha, red, green and blue components.
+    pub fn from_argb(alpha: u8, red: u8, green: u8, blue: u8) -> Self {
+        Color(u32::from_be_bytes([alpha, red, green, blue]))
+    }
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "0x{:08x}", self.0)
+    }
+}
+
+impl FromStr for Color {
+    type Err = YabaiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let hex = s
+            .strip_prefix("0x")
+            .or_else(|| s.strip_prefix("0X"))
+            .unwrap_or(s);
+
+        match u32::from_str_radix(hex, 16) {
+            Ok(color) if hex.len() == 8 && hex.bytes().all(|b| b.is_ascii_hexdigit()) => {
+                Ok(Color(color))
+            }
+            _ => Err(YabaiError::InvalidArgument(format!(
+                "{} is not a color in 0xAARRGGBB format",
+                s
+            ))),
+        }
+    }
+}
+
+impl From<u32> for Color {
+    fn from(color: u32) -> Self {
+        Color(color)
+    }
+}
+
+/// Returns the `on` or `off` value yabai uses for boolean settings.
+pub(crate) fn on_off(value: bool) -> &'static str {
+    if value {
+        "on"
+    } else {
+        "off"
+    }
+}
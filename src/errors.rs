@@ -1,11 +1,132 @@
+use std::path::PathBuf;
 use thiserror::Error;
 
 /// The main error type for errors returned by this crate.
+///
+/// New variants may be added, such as for errors behind features, so matches need a
+/// wildcard arm.
 #[derive(Error, Debug)]
-
+#[non_exhaustive]
 pub enum YabaiError {
-    #[error("IO Error: {0}")]
+    /// yabai accepted the message, but its response couldn't be parsed.
+    #[error("FormatError: {0}")]
     FormatError(String),
+    /// yabai rejected the command, returning an error message.
     #[error("CommandError: {command:?} caused {message:?}")]
     CommandError { command: String, message: String },
+    /// An argument was rejected before anything was sent to yabai.
+    #[error("InvalidArgument: {0}")]
+    InvalidArgument(String),
+    /// No socket path was configured, and the default one couldn't be found without `USER`.
+    #[error("UserEnvMissing: USER is not set and no socket path was configured")]
+    UserEnvMissing,
+    /// No socket exists at the socket path, usually because yabai isn't running.
+    ///
+    /// ```
+    /// let client = yabai::YabaiClient::with_socket_path("/nonexistent/yabai.socket");
+    /// let error = client.send("query --spaces").unwrap_err();
+    ///
+    /// assert!(matches!(error, yabai::YabaiError::SocketNotFound(_)));
+    /// ```
+    #[error("SocketNotFound: no yabai socket at {0:?}, is yabai running?")]
+    SocketNotFound(PathBuf),
+    /// yabai didn't respond before the client's timeout elapsed.
+    #[error("Timeout: yabai did not respond in time")]
+    Timeout,
+    /// Communicating with the yabai socket failed.
+    #[error("Io: {0}")]
+    Io(#[from] std::io::Error),
+    /// yabai's response couldn't be deserialized.
+    #[cfg(feature = "serde")]
+    #[error("Json: {0}")]
+    Json(#[from] serde_json::Error),
+    /// yabai didn't respond to a command that was expected to return a result.
+    #[error("EmptyResponse: yabai did not return a result")]
+    EmptyResponse,
+    /// Nothing yabai reported matched what was looked for, such as a focused space.
+    #[error("NotFound: {0}")]
+    NotFound(String),
+}
+
+impl YabaiError {
+    /// Returns the category of a `CommandError`, parsed from the message yabai returned.
+    ///
+    /// Errors other than `CommandError` return `None`.
+    ///
+    /// ```
+    /// use yabai::{YabaiError, YabaiErrorKind};
+    ///
+    /// let error = YabaiError::CommandError {
+    ///     command: "space --focus 2".to_string(),
+    ///     message: "cannot focus an already focused space.".to_string(),
+    /// };
+    ///
+    /// assert_eq!(error.kind(), Some(YabaiErrorKind::AlreadyDone));
+    /// ```
+    pub fn kind(&self) -> Option<YabaiErrorKind> {
+        match self {
+            YabaiError::CommandError { message, .. } => Some(YabaiErrorKind::from_message(message)),
+            _ => None,
+        }
+    }
+}
+
+/// An **enum** categorizing the error messages yabai returns for failed commands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum YabaiErrorKind {
+    /// The selected window doesn't exist.
+    WindowNotFound,
+    /// The selected space doesn't exist.
+    SpaceNotFound,
+    /// The selected display doesn't exist.
+    DisplayNotFound,
+    /// The command would have no effect, such as focusing the already focused space.
+    AlreadyDone,
+    /// yabai doesn't recognize the command or domain.
+    UnknownCommand,
+    /// A value given to the command isn't valid.
+    InvalidValue,
+    /// Any other error.
+    Other,
+}
+
+impl YabaiErrorKind {
+    /// Categorizes an error message returned by yabai, falling back to `Other` for messages
+    /// that aren't recognized.
+    ///
+    /// ```
+    /// use yabai::YabaiErrorKind;
+    ///
+    /// assert_eq!(
+    ///     YabaiErrorKind::from_message("could not locate the selected window."),
+    ///     YabaiErrorKind::WindowNotFound
+    /// );
+    /// assert_eq!(YabaiErrorKind::from_message("something else"), YabaiErrorKind::Other);
+    /// ```
+    pub fn from_message(message: &str) -> Self {
+        let message = message.to_lowercase();
+
+        if message.contains("already") {
+            YabaiErrorKind::AlreadyDone
+        } else if message.contains("could not locate") {
+            if message.contains("window") {
+                YabaiErrorKind::WindowNotFound
+            } else if message.contains("space") {
+                YabaiErrorKind::SpaceNotFound
+            } else if message.contains("display") {
+                YabaiErrorKind::DisplayNotFound
+            } else {
+                YabaiErrorKind::Other
+            }
+        } else if message.contains("unknown command") || message.contains("unknown domain") {
+            YabaiErrorKind::UnknownCommand
+        } else if message.contains("unknown value")
+            || message.contains("invalid value")
+            || message.contains("not a valid")
+        {
+            YabaiErrorKind::InvalidValue
+        } else {
+            YabaiErrorKind::Other
+        }
+    }
 }
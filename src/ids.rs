@@ -0,0 +1,92 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+/// The id of a window, as reported in `WindowInfo::id`.
+///
+/// Distinct from [`SpaceIndex`] and [`DisplayIndex`], so that one can't accidentally be
+/// passed where another is expected. Existing numeric values convert with `.into()`:
+///
+/// ```no_run
+/// yabai::focus_window(4521.into())?;
+/// # Ok::<(), yabai::YabaiError>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+#[repr(transparent)]
+#[cfg_attr(feature = "python", pyclass)]
+pub struct WindowId(pub u32);
+
+/// The mission control index of a space, as reported in `SpaceInfo::index`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+#[repr(transparent)]
+#[cfg_attr(feature = "python", pyclass)]
+pub struct SpaceIndex(pub u32);
+
+/// The arrangement index of a display, as reported in `DisplayInfo::index`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+#[repr(transparent)]
+#[cfg_attr(feature = "python", pyclass)]
+pub struct DisplayIndex(pub u32);
+
+impl From<u32> for WindowId {
+    fn from(id: u32) -> Self {
+        WindowId(id)
+    }
+}
+
+impl From<u32> for SpaceIndex {
+    fn from(index: u32) -> Self {
+        SpaceIndex(index)
+    }
+}
+
+impl From<u32> for DisplayIndex {
+    fn from(index: u32) -> Self {
+        DisplayIndex(index)
+    }
+}
+
+impl From<WindowId> for u32 {
+    fn from(id: WindowId) -> Self {
+        id.0
+    }
+}
+
+impl From<SpaceIndex> for u32 {
+    fn from(index: SpaceIndex) -> Self {
+        index.0
+    }
+}
+
+impl From<DisplayIndex> for u32 {
+    fn from(index: DisplayIndex) -> Self {
+        index.0
+    }
+}
+
+impl fmt::Display for WindowId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl fmt::Display for SpaceIndex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl fmt::Display for DisplayIndex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
@@ -0,0 +1,91 @@
+//! Geometry helpers for computing window frames, independent of yabai.
+//!
+//! The frames can be sent as `Command::MoveWindow` and `Command::ResizeWindow` commands to
+//! arrange windows in a custom layout:
+//!
+//! ```
+//! use yabai::{layout, Frame};
+//!
+//! let display = Frame::from_xywh(0.0, 0.0, 1200.0, 800.0);
+//! let frames = layout::grid_frames(display, 2, 2);
+//!
+//! assert_eq!(frames[0], Frame::from_xywh(0.0, 0.0, 600.0, 400.0));
+//! assert_eq!(frames[3], Frame::from_xywh(600.0, 400.0, 600.0, 400.0));
+//! ```
+
+use crate::Frame;
+
+/// Splits the area into a grid of equally sized cells, in rows from left to right.
+///
+/// Returns no frames if `rows` or `cols` is zero.
+pub fn grid_frames(area: Frame, rows: u32, cols: u32) -> Vec<Frame> {
+    grid_frames_with_gap(area, rows, cols, 0.0)
+}
+
+/// Splits the area into a grid of equally sized cells with `gap` between neighboring cells, in
+/// rows from left to right.
+///
+/// Cells are never smaller than zero, so gaps wider than the area leave every cell empty:
+///
+/// ```
+/// use yabai::{layout, Frame};
+///
+/// let frames = layout::grid_frames_with_gap(Frame::from_xywh(0.0, 0.0, 210.0, 100.0), 1, 2, 10.0);
+///
+/// assert_eq!(
+///     frames,
+///     vec![
+///         Frame::from_xywh(0.0, 0.0, 100.0, 100.0),
+///         Frame::from_xywh(110.0, 0.0, 100.0, 100.0),
+///     ]
+/// );
+///
+/// let frames = layout::grid_frames_with_gap(Frame::from_xywh(0.0, 0.0, 10.0, 100.0), 1, 2, 20.0);
+/// assert!(frames.iter().all(|frame| frame.w == 0.0 && frame.h == 100.0));
+/// ```
+pub fn grid_frames_with_gap(area: Frame, rows: u32, cols: u32, gap: f32) -> Vec<Frame> {
+    if rows == 0 || cols == 0 {
+        return Vec::new();
+    }
+
+    let w = ((area.w - gap * (cols - 1) as f32) / cols as f32).max(0.0);
+    let h = ((area.h - gap * (rows - 1) as f32) / rows as f32).max(0.0);
+
+    (0..rows)
+        .flat_map(|row| {
+            (0..cols).map(move |col| Frame {
+                x: area.x + col as f32 * (w + gap),
+                y: area.y + row as f32 * (h + gap),
+                w,
+                h,
+            })
+        })
+        .collect()
+}
+
+/// Splits the area into `n` equally wide columns, from left to right.
+pub fn columns(area: Frame, n: u32) -> Vec<Frame> {
+    grid_frames(area, 1, n)
+}
+
+/// Splits the area into `n` equally tall rows, from top to bottom.
+pub fn rows(area: Frame, n: u32) -> Vec<Frame> {
+    grid_frames(area, n, 1)
+}
+
+/// Shrinks the area by `padding` on every side, as yabai does with space padding.
+///
+/// ```
+/// use yabai::{layout, Frame};
+///
+/// let area = layout::pad(Frame::from_xywh(0.0, 0.0, 100.0, 100.0), 10.0);
+/// assert_eq!(area, Frame::from_xywh(10.0, 10.0, 80.0, 80.0));
+/// ```
+pub fn pad(area: Frame, padding: f32) -> Frame {
+    Frame {
+        x: area.x + padding,
+        y: area.y + padding,
+        w: (area.w - padding * 2.0).max(0.0),
+        h: (area.h - padding * 2.0).max(0.0),
+    }
+}
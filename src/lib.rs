@@ -4,43 +4,147 @@
 //! ## Examples:
 //!
 //! Send a command as a string:
-//! ```
-//! yabai::send("--focus space 2");
+//! ```no_run
+//! yabai::send("space --focus 2")?;
+//! # Ok::<(), yabai::YabaiError>(())
 //!```
 //!
 //! Send a command using the `yabai::Command` type:
-//! ```
-//! let command: yabai::Command = yabai::Command::FocusSpace(yabai::SpaceOption::Recent);
-//! yabai::send_command(command)?;
+//! ```no_run
+//! let command = yabai::Command::FocusSpace {
+//!     option: yabai::FocusSpaceOption::Recent {},
+//! };
+//! yabai::send_command(&command)?;
+//! # Ok::<(), yabai::YabaiError>(())
 //! ```
 //!
 //! Query yabai for display information:
-//! ```
+//! ```no_run
+//! # #[cfg(feature = "serde")]
+//! # {
 //! let displays = yabai::query_displays()?;
+//! # }
+//! # Ok::<(), yabai::YabaiError>(())
 //! ```
 //!
+#[cfg(feature = "tokio")]
+mod async_client;
+mod client;
+mod command_builder;
+mod command_parser;
 mod commands;
+mod config;
 mod errors;
+mod ids;
+pub mod layout;
+#[cfg(test)]
+mod mock;
+pub mod protocol;
+#[cfg(feature = "serde")]
+mod query;
+mod rules;
+mod selectors;
+mod signals;
+mod subscription;
 
+pub use client::*;
+pub use command_builder::*;
 pub use commands::*;
+pub use config::*;
 pub use errors::*;
+pub use ids::*;
+#[cfg(feature = "serde")]
+pub use query::*;
+pub use rules::*;
+pub use selectors::*;
+pub use signals::*;
+pub use subscription::*;
 
-use anyhow::anyhow;
-use byteorder::{LittleEndian, WriteBytesExt};
 use lazy_static::lazy_static;
+#[cfg(all(feature = "tokio", feature = "serde"))]
+use serde::de::DeserializeOwned;
 use std::{
-    io::{Read, Write},
-    os::unix::net::UnixStream,
     path::PathBuf,
+    sync::{Mutex, PoisonError},
+    time::Duration,
 };
 
 lazy_static! {
-    static ref SOCKET_PATH: PathBuf = {
-        PathBuf::from(format!(
-            "/tmp/yabai_{}.socket",
-            std::env::var("USER").unwrap()
-        ))
-    };
+    static ref SOCKET_PATH_OVERRIDE: Mutex<Option<PathBuf>> = Mutex::new(None);
+}
+
+/// Overrides the path of the yabai socket used by this crate.
+///
+/// Takes precedence over the `YABAI_SOCKET_PATH` environment variable and the default path.
+pub fn set_socket_path(path: PathBuf) {
+    *SOCKET_PATH_OVERRIDE
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner) = Some(path);
+}
+
+/// Returns the path of the yabai socket.
+///
+/// The path is resolved in order from:
+/// 1. A path set with `yabai::set_socket_path`.
+/// 2. The `YABAI_SOCKET_PATH` environment variable.
+/// 3. The default `/tmp/yabai_$USER.socket` path.
+pub fn socket_path() -> Result<PathBuf, YabaiError> {
+    let path_override = SOCKET_PATH_OVERRIDE
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone();
+
+    if let Some(path) = path_override {
+        return Ok(path);
+    }
+
+    if let Some(path) = std::env::var_os("YABAI_SOCKET_PATH") {
+        return Ok(PathBuf::from(path));
+    }
+
+    match std::env::var("USER") {
+        Ok(user) => Ok(PathBuf::from(format!("/tmp/yabai_{}.socket", user))),
+        Err(_) => Err(YabaiError::UserEnvMissing),
+    }
+}
+
+/// Returns whether yabai is accepting connections on the default socket.
+///
+/// Example:
+///
+/// ```no_run
+/// if !yabai::is_yabai_running() {
+///     eprintln!("start yabai first");
+/// }
+/// ```
+pub fn is_yabai_running() -> bool {
+    YabaiClient::new().is_yabai_running()
+}
+
+/// Returns the version of the `yabai` binary on the `PATH`, such as `yabai-v7.1.0`.
+///
+/// yabai doesn't report its version over the socket, so this runs `yabai --version` rather
+/// than asking the yabai a client talks to. With a custom socket path, the reported binary
+/// may not be the one running on that socket, and an error is returned if `yabai` isn't on
+/// the `PATH` even when the socket works.
+///
+/// ```no_run
+/// println!("{}", yabai::installed_version()?);
+/// # Ok::<(), yabai::YabaiError>(())
+/// ```
+pub fn installed_version() -> Result<String, YabaiError> {
+    let output = std::process::Command::new("yabai")
+        .arg("--version")
+        .output()?;
+
+    if !output.status.success() {
+        return Err(YabaiError::CommandError {
+            command: "yabai --version".to_string(),
+            message: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        });
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
 }
 
 /// Send a command to yabai as a string of space-separated arguments.
@@ -49,114 +153,302 @@ lazy_static! {
 ///
 /// Example:
 ///
+/// ```no_run
+/// yabai::send("space --focus 2")?;
+/// # Ok::<(), yabai::YabaiError>(())
 /// ```
-/// yabai::send_str("space --focus 2")?;
+pub fn send(message: &str) -> Result<Option<String>, YabaiError> {
+    YabaiClient::new().send(message)
+}
+
+/// Send a command to yabai as a string of space-separated arguments, giving up with a
+/// `YabaiError::Timeout` if yabai doesn't respond within the timeout.
+///
+/// A zero timeout returns a `YabaiError::InvalidArgument`.
+pub fn send_with_timeout(message: &str, timeout: Duration) -> Result<Option<String>, YabaiError> {
+    YabaiClient::new().with_timeout(timeout)?.send(message)
+}
+
+/// Send a command to yabai as a string of space-separated arguments, retrying up to `retries`
+/// times with `delay` between attempts if yabai can't be connected to.
+///
+/// See `YabaiClient::with_retry` for which failures are retried.
+pub fn send_with_retry(
+    message: &str,
+    retries: u32,
+    delay: Duration,
+) -> Result<Option<String>, YabaiError> {
+    YabaiClient::new().with_retry(retries, delay).send(message)
+}
+
+/// Send a command to yabai as a list of arguments.
+///
+/// Unlike `send`, arguments are not split on whitespace, so they may contain spaces.
+///
+/// Example:
+///
+/// ```no_run
+/// yabai::send_args(&["rule", "--add", "app=Google Chrome", "manage=off"])?;
+/// # Ok::<(), yabai::YabaiError>(())
 /// ```
-pub fn send(message: &str) -> anyhow::Result<Option<String>> {
-    send_raw(&format!(
-        "{}\0\0",
-        message.trim().split(' ').collect::<Vec<&str>>().join("\0")
-    ))
+pub fn send_args<S: AsRef<str>>(args: &[S]) -> Result<Option<String>, YabaiError> {
+    YabaiClient::new().send_args(args)
 }
 
-fn send_raw(command: &str) -> anyhow::Result<Option<String>> {
-    let mut buffer = Vec::new();
-    let mut stream = UnixStream::connect(SOCKET_PATH.as_path())?;
+/// Send an already framed message to yabai, prefixed with its length.
+///
+/// The bytes must already end each argument and the message with a NUL byte. See
+/// `yabai::protocol::write_framed`.
+pub fn send_framed(bytes: &[u8]) -> Result<Option<String>, YabaiError> {
+    YabaiClient::new().send_framed(bytes)
+}
 
-    stream.write_u32::<LittleEndian>(command.len() as u32)?;
-    stream.write_all(command.as_bytes())?;
+/// Send a `yabai::Command` to yabai.
+///
+/// Example:
+///
+/// ```no_run
+/// let command = yabai::Command::FocusSpace {
+///     option: yabai::FocusSpaceOption::Recent {},
+/// };
+/// yabai::send_command(&command)?;
+/// # Ok::<(), yabai::YabaiError>(())
+/// ```
+pub fn send_command(command: &Command) -> Result<Option<String>, YabaiError> {
+    YabaiClient::new().send_command(command)
+}
 
-    let bytes = stream.read_to_end(&mut buffer)?;
+/// Sends each command to yabai in order, returning a result for each command.
+///
+/// See `YabaiClient::send_batch` for details.
+pub fn send_batch(commands: &[Command]) -> Vec<Result<Option<String>, YabaiError>> {
+    YabaiClient::new().send_batch(commands)
+}
 
-    if bytes == 0 {
-        return Ok(None);
-    }
+/// Returns the current value of a config setting.
+///
+/// Example:
+///
+/// ```no_run
+/// let gap = yabai::get_config(yabai::ConfigKey::WindowGap)?;
+/// # Ok::<(), yabai::YabaiError>(())
+/// ```
+pub fn get_config(key: ConfigKey) -> Result<String, YabaiError> {
+    YabaiClient::new().get_config(key)
+}
 
-    if buffer[0] == 0x07 {
-        let rest = buffer[1..].to_vec();
-        let error_message = String::from_utf8(rest)?;
+/// Returns the current value of an `on` or `off` config setting.
+pub fn get_config_bool(key: ConfigKey) -> Result<bool, YabaiError> {
+    YabaiClient::new().get_config_bool(key)
+}
 
-        let error = YabaiError::CommandError {
-            command: command.to_string(),
-            message: error_message,
-        };
+/// Returns the current value of a numeric config setting.
+pub fn get_config_f32(key: ConfigKey) -> Result<f32, YabaiError> {
+    YabaiClient::new().get_config_f32(key)
+}
 
-        return Err(anyhow!(error));
-    }
+/// Returns the current value of a whole number config setting.
+pub fn get_config_u32(key: ConfigKey) -> Result<u32, YabaiError> {
+    YabaiClient::new().get_config_u32(key)
+}
 
-    Ok(Some(String::from_utf8(buffer)?))
+/// Returns the current value of a config setting for a single space.
+pub fn get_space_config(space: &SpaceSelector, key: ConfigKey) -> Result<String, YabaiError> {
+    YabaiClient::new().get_space_config(space, key)
 }
 
-/// Send a `yabai::Command` to yabai.
+/// Sets a config setting for a single space.
 ///
 /// Example:
 ///
+/// ```no_run
+/// let space = yabai::SpaceSelector::Label {
+///     label: "code".to_string(),
+/// };
+/// yabai::set_space_config(&space, yabai::ConfigKey::WindowGap, "12")?;
+/// # Ok::<(), yabai::YabaiError>(())
 /// ```
-/// let command = yabai::Command::FocusSpace(yabai::SpaceOption::Recent);
-/// yabai::send_command(command)?;
+pub fn set_space_config(
+    space: &SpaceSelector,
+    key: ConfigKey,
+    value: &str,
+) -> Result<Option<String>, YabaiError> {
+    YabaiClient::new().set_space_config(space, key, value)
+}
+
+/// Sets a config setting.
+pub fn set_config(key: ConfigKey, value: &str) -> Result<Option<String>, YabaiError> {
+    YabaiClient::new().set_config(key, value)
+}
+
+/// Sets whether the mouse moves to the center of windows as they're focused.
+///
+/// Example:
+///
+/// ```no_run
+/// yabai::set_mouse_follows_focus(true)?;
+/// # Ok::<(), yabai::YabaiError>(())
 /// ```
-pub fn send_command(command: &Command) -> anyhow::Result<Option<String>> {
-    let result = match command {
-        Command::FocusSpace { option } => match option {
-            FocusSpaceOption::Space { space } => send(&format!("space --focus {}", space))?,
-            named_option => send(&format!("space --focus {named_option}"))?,
-        },
-        Command::RotateSpace { rotation } => send(&format!("space --rotate {}", rotation))?,
-        Command::BalanceSpace {} => send("space --balance")?,
-        Command::MoveActiveWindowToSpace { space } => send(&format!("window --space {}", space))?,
-        Command::FocusWindow { window } => send(&format!("window --focus {}", window))?,
-        Command::FocusWindowDirection { direction } => {
-            send(&format!("window --focus {}", direction))?
-        }
-        Command::SwapWindowDirection { direction } => {
-            send(&format!("window --swap {}", direction))?
-        }
-        Command::WarpWindowDirection { direction } => {
-            send(&format!("window --warp {}", direction))?
-        }
-        Command::ToggleWindowFloating {} => send("window --toggle float")?,
-        Command::ToggleZoomFullscreen {} => send("window --toggle zoom-fullscreen")?,
-    };
-
-    Ok(result)
-}
-
-/// Queries yabai for information about all spaces.
-pub fn query_spaces() -> anyhow::Result<Vec<SpaceInfo>> {
-    let result = send("query --spaces")?;
-
-    match result {
-        Some(str) => Ok(serde_json::from_str::<Vec<SpaceInfo>>(&str)?),
-        None => Err(anyhow!("No result from yabai query --spaces")),
-    }
+pub fn set_mouse_follows_focus(enabled: bool) -> Result<Option<String>, YabaiError> {
+    YabaiClient::new().set_mouse_follows_focus(enabled)
 }
 
-/// Queries yabai for information about all displays.
-pub fn query_displays() -> anyhow::Result<Vec<DisplayInfo>> {
-    let result = send("query --displays")?;
+/// Sets whether windows are focused as the mouse moves over them, and whether they're raised.
+pub fn set_focus_follows_mouse(mode: FocusFollowsMouse) -> Result<Option<String>, YabaiError> {
+    YabaiClient::new().set_focus_follows_mouse(mode)
+}
 
-    match result {
-        Some(str) => Ok(serde_json::from_str::<Vec<DisplayInfo>>(&str)?),
-        None => Err(anyhow!("No result from yabai query --displays")),
-    }
+/// Sets whether windows draw shadows.
+pub fn set_window_shadow(enabled: bool) -> Result<Option<String>, YabaiError> {
+    YabaiClient::new().set_window_shadow(enabled)
+}
+
+/// Sets whether the windows on a space are balanced automatically.
+pub fn set_auto_balance(enabled: bool) -> Result<Option<String>, YabaiError> {
+    YabaiClient::new().set_auto_balance(enabled)
 }
 
-/// Queries yabai for information about all windows.
-pub fn query_windows() -> anyhow::Result<Vec<WindowInfo>> {
-    let result = send("query --windows")?;
+/// Sets whether yabai draws borders around windows, which yabai 6 removed.
+pub fn set_window_border(enabled: bool) -> Result<Option<String>, YabaiError> {
+    YabaiClient::new().set_window_border(enabled)
+}
 
-    match result {
-        Some(str) => Ok(serde_json::from_str::<Vec<WindowInfo>>(&str)?),
-        None => Err(anyhow!("No result from yabai query --windows")),
-    }
+/// Sets the width of window borders, in points.
+pub fn set_window_border_width(width: u32) -> Result<Option<String>, YabaiError> {
+    YabaiClient::new().set_window_border_width(width)
+}
+
+/// Sets the border color of the focused window.
+pub fn set_active_window_border_color(color: Color) -> Result<Option<String>, YabaiError> {
+    YabaiClient::new().set_active_window_border_color(color)
+}
+
+/// Sets the border color of windows that aren't focused.
+pub fn set_normal_window_border_color(color: Color) -> Result<Option<String>, YabaiError> {
+    YabaiClient::new().set_normal_window_border_color(color)
+}
+
+/// Sets the color of the overlay shown where a window will be inserted.
+pub fn set_insert_feedback_color(color: Color) -> Result<Option<String>, YabaiError> {
+    YabaiClient::new().set_insert_feedback_color(color)
+}
+
+/// Subscribes to the given events, returning an iterator over them as yabai reports them.
+///
+/// Example:
+///
+/// ```no_run
+/// for event in yabai::subscribe(&[yabai::Event::WindowFocused])? {
+///     println!("focused {:?}", event.variable("YABAI_WINDOW_ID"));
+/// }
+/// # Ok::<(), yabai::YabaiError>(())
+/// ```
+pub fn subscribe(events: &[Event]) -> Result<Subscription, YabaiError> {
+    YabaiClient::new().subscribe(events)
+}
+
+pub fn focus_window(window: WindowId) -> Result<Option<String>, YabaiError> {
+    YabaiClient::new().focus_window(window)
+}
+
+/// Focuses the selected window, falling back to focusing `fallback` if yabai can't find a
+/// window to focus.
+///
+/// Example, focusing the window to the west, or the last window on the space if there isn't
+/// one:
+///
+/// ```no_run
+/// use yabai::{Direction, WindowSelector};
+///
+/// yabai::focus_window_or(Direction::West.into(), WindowSelector::Last {})?;
+/// # Ok::<(), yabai::YabaiError>(())
+/// ```
+pub fn focus_window_or(
+    window: WindowSelector,
+    fallback: WindowSelector,
+) -> Result<Option<String>, YabaiError> {
+    YabaiClient::new().focus_window_or(window, fallback)
+}
+
+/// Focuses the window under the mouse (`window --focus mouse`).
+pub fn focus_mouse_window() -> Result<Option<String>, YabaiError> {
+    YabaiClient::new().focus_mouse_window()
+}
+
+/// Focuses the previously focused window (`window --focus recent`).
+pub fn focus_recent_window() -> Result<Option<String>, YabaiError> {
+    YabaiClient::new().focus_recent_window()
+}
+
+pub fn focus_space(space: SpaceIndex) -> Result<Option<String>, YabaiError> {
+    YabaiClient::new().focus_space(space)
+}
+
+/// Moves the focused window to the selected space, then focuses that space if the window
+/// moved.
+pub fn move_window_to_space_and_follow(space: SpaceSelector) -> Result<(), YabaiError> {
+    YabaiClient::new().move_window_to_space_and_follow(space)
+}
+
+/// Toggles picture-in-picture for the focused window.
+pub fn toggle_pip() -> Result<Option<String>, YabaiError> {
+    YabaiClient::new().toggle_pip()
+}
+
+/// Toggles float, sticky, topmost and picture-in-picture for the focused window, returning
+/// the first toggle yabai fails to apply.
+pub fn make_floating_overlay() -> Result<(), YabaiError> {
+    YabaiClient::new().make_floating_overlay()
+}
+
+/// Send a command to yabai as a string of space-separated arguments, without blocking.
+#[cfg(feature = "tokio")]
+pub async fn send_async(message: &str) -> Result<Option<String>, YabaiError> {
+    YabaiClient::new().send_async(message).await
+}
+
+/// Send a command to yabai as a list of arguments, without blocking.
+#[cfg(feature = "tokio")]
+pub async fn send_args_async<S: AsRef<str>>(args: &[S]) -> Result<Option<String>, YabaiError> {
+    YabaiClient::new().send_args_async(args).await
+}
+
+/// Send a `yabai::Command` to yabai, without blocking.
+///
+/// Example:
+///
+/// ```no_run
+/// # async fn run() -> Result<(), yabai::YabaiError> {
+/// let command = yabai::Command::BalanceSpace {};
+/// yabai::send_command_async(&command).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "tokio")]
+pub async fn send_command_async(command: &Command) -> Result<Option<String>, YabaiError> {
+    YabaiClient::new().send_command_async(command).await
+}
+
+/// Sends a query to yabai and deserializes the JSON response, without blocking.
+#[cfg(all(feature = "tokio", feature = "serde"))]
+pub async fn query_async<T: DeserializeOwned>(message: &str) -> Result<T, YabaiError> {
+    YabaiClient::new().query_async(message).await
+}
+
+/// Queries yabai for information about all spaces, without blocking.
+#[cfg(all(feature = "tokio", feature = "serde"))]
+pub async fn query_spaces_async() -> Result<Vec<SpaceInfo>, YabaiError> {
+    YabaiClient::new().query_spaces_async().await
 }
 
-pub fn focus_window(window: u32) -> anyhow::Result<Option<String>> {
-    send_command(&Command::FocusWindow { window })
+/// Queries yabai for information about all displays, without blocking.
+#[cfg(all(feature = "tokio", feature = "serde"))]
+pub async fn query_displays_async() -> Result<Vec<DisplayInfo>, YabaiError> {
+    YabaiClient::new().query_displays_async().await
 }
 
-pub fn focus_space(space: u32) -> anyhow::Result<Option<String>> {
-    send_command(&Command::FocusSpace {
-        option: FocusSpaceOption::Space { space },
-    })
+/// Queries yabai for information about all windows, without blocking.
+#[cfg(all(feature = "tokio", feature = "serde"))]
+pub async fn query_windows_async() -> Result<Windows, YabaiError> {
+    YabaiClient::new().query_windows_async().await
 }
@@ -0,0 +1,81 @@
+//! A mock yabai socket used by the tests.
+
+use crate::{protocol::describe_framed, YabaiClient};
+use std::{
+    io::{self, Read, Write},
+    os::unix::net::UnixListener,
+    path::PathBuf,
+    sync::atomic::{AtomicUsize, Ordering},
+    thread::JoinHandle,
+};
+
+/// Counts mocks started by this process, so each gets its own socket.
+static MOCK_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+/// A socket that answers each connection with the next canned reply, as yabai would.
+pub(crate) struct MockYabai {
+    path: PathBuf,
+    server: Option<JoinHandle<io::Result<Vec<String>>>>,
+}
+
+impl MockYabai {
+    /// Starts a mock that accepts one connection for each reply, answering them in order.
+    pub(crate) fn start(replies: &[&[u8]]) -> io::Result<Self> {
+        let path = std::env::temp_dir().join(format!(
+            "yabai-mock-{}-{}.socket",
+            std::process::id(),
+            MOCK_COUNT.fetch_add(1, Ordering::Relaxed)
+        ));
+        let _ = std::fs::remove_file(&path);
+
+        let listener = UnixListener::bind(&path)?;
+        let replies = replies
+            .iter()
+            .map(|reply| reply.to_vec())
+            .collect::<Vec<_>>();
+
+        let server = std::thread::spawn(move || {
+            let mut messages = Vec::new();
+
+            for reply in replies {
+                let (mut stream, _) = listener.accept()?;
+
+                let mut length = [0; 4];
+                stream.read_exact(&mut length)?;
+
+                let mut message = vec![0; u32::from_le_bytes(length) as usize];
+                stream.read_exact(&mut message)?;
+                messages.push(describe_framed(&message));
+
+                stream.write_all(&reply)?;
+            }
+
+            Ok(messages)
+        });
+
+        Ok(Self {
+            path,
+            server: Some(server),
+        })
+    }
+
+    /// Returns a client connecting to the mock.
+    pub(crate) fn client(&self) -> YabaiClient {
+        YabaiClient::with_socket_path(&self.path)
+    }
+
+    /// Waits until every reply has been sent, returning the messages received as their
+    /// space-separated arguments.
+    pub(crate) fn finish(mut self) -> io::Result<Vec<String>> {
+        match self.server.take().map(JoinHandle::join) {
+            Some(Ok(messages)) => messages,
+            _ => Err(io::Error::other("mock yabai failed")),
+        }
+    }
+}
+
+impl Drop for MockYabai {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
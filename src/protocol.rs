@@ -0,0 +1,274 @@
+//! The framing used to talk to yabai over its socket, usable with any stream.
+//!
+//! A message is the length of the message as a little-endian `u32`, followed by each argument
+//! terminated with a NUL byte, and a final NUL byte. yabai writes its response without a
+//! length prefix and closes the connection, prefixing the response with `0x07` when the command
+//! fails.
+//!
+//! ```
+//! use std::io::Cursor;
+//!
+//! let mut message = Vec::new();
+//! yabai::protocol::write_message(&mut message, "config window_gap")?;
+//! assert_eq!(message, b"\x13\0\0\0config\0window_gap\0\0");
+//!
+//! let response = yabai::protocol::read_response(&mut Cursor::new(b"2\n"))?;
+//! assert_eq!(response.as_deref(), Some("2"));
+//! # Ok::<(), yabai::YabaiError>(())
+//! ```
+
+use crate::YabaiError;
+use byteorder::{LittleEndian, WriteBytesExt};
+use std::io::{self, Read, Write};
+
+/// Writes a message of space-separated arguments.
+pub fn write_message<W: Write>(writer: &mut W, message: &str) -> Result<(), YabaiError> {
+    write_args(writer, &split_message(message))
+}
+
+/// Writes a message of arguments, passing each argument through as-is.
+///
+/// ```
+/// let mut message = Vec::new();
+/// yabai::protocol::write_args(&mut message, &["space", "--label", "my space"])?;
+/// assert_eq!(message, b"\x18\0\0\0space\0--label\0my space\0\0");
+/// # Ok::<(), yabai::YabaiError>(())
+/// ```
+pub fn write_args<W: Write, S: AsRef<str>>(writer: &mut W, args: &[S]) -> Result<(), YabaiError> {
+    let args = args.iter().map(AsRef::as_ref).collect::<Vec<&str>>();
+
+    write_framed(writer, encode_args(&args).as_bytes())
+}
+
+/// Writes an already framed message, prefixed with its length.
+///
+/// The bytes are written exactly as given, so they must already contain the NUL byte ending
+/// each argument and the final NUL byte ending the message. Use `write_args` to have these
+/// added instead; adding them to the bytes as well would terminate the message twice.
+///
+/// ```
+/// let mut message = Vec::new();
+/// yabai::protocol::write_framed(&mut message, b"space\0--balance\0\0")?;
+/// assert_eq!(message, b"\x11\0\0\0space\0--balance\0\0");
+/// # Ok::<(), yabai::YabaiError>(())
+/// ```
+pub fn write_framed<W: Write>(writer: &mut W, bytes: &[u8]) -> Result<(), YabaiError> {
+    writer
+        .write_u32::<LittleEndian>(bytes.len() as u32)
+        .map_err(map_timeout)?;
+    writer.write_all(bytes).map_err(map_timeout)?;
+
+    Ok(())
+}
+
+/// Reads a response until the end of the stream.
+///
+/// Returns `None` if yabai wrote nothing, as it does for commands that succeed silently, or
+/// only wrote the trailing newline and NUL byte, so a response is never an empty string:
+///
+/// ```
+/// use std::io::Cursor;
+///
+/// let response = yabai::protocol::read_response(&mut std::io::empty())?;
+/// assert_eq!(response, None);
+///
+/// let response = yabai::protocol::read_response(&mut Cursor::new(b"\n\0"))?;
+/// assert_eq!(response, None);
+/// # Ok::<(), yabai::YabaiError>(())
+/// ```
+///
+/// A single trailing newline and NUL byte are removed, and bytes that aren't valid UTF-8 are
+/// replaced with `U+FFFD`:
+///
+/// ```
+/// use std::io::Cursor;
+///
+/// let response = yabai::protocol::read_response(&mut Cursor::new(b"line\n\n\0"))?;
+/// assert_eq!(response.as_deref(), Some("line\n"));
+///
+/// let response = yabai::protocol::read_response(&mut Cursor::new(b"caf\xff"))?;
+/// assert_eq!(response.as_deref(), Some("caf\u{FFFD}"));
+/// # Ok::<(), yabai::YabaiError>(())
+/// ```
+///
+/// yabai doesn't prefix responses with their length, so reading loops until yabai closes the
+/// connection, however many reads the response arrives in. Large responses, such as a
+/// `query --windows` on a busy system, are never truncated.
+///
+/// A response starting with `0x07` is returned as a `YabaiError::CommandError`. As the message
+/// isn't known to the reader, its `command` is empty:
+///
+/// ```
+/// use std::io::Cursor;
+///
+/// let error = yabai::protocol::read_response(&mut Cursor::new(b"\x07unknown domain 'nope'"))
+///     .unwrap_err();
+///
+/// assert!(matches!(
+///     error,
+///     yabai::YabaiError::CommandError { message, .. } if message == "unknown domain 'nope'"
+/// ));
+/// ```
+pub fn read_response<R: Read>(reader: &mut R) -> Result<Option<String>, YabaiError> {
+    read_command_response(reader, "")
+}
+
+/// Reads a response, naming the command that was sent in a returned `YabaiError::CommandError`.
+pub(crate) fn read_command_response<R: Read>(
+    reader: &mut R,
+    command: &str,
+) -> Result<Option<String>, YabaiError> {
+    let mut buffer = Vec::new();
+    reader.read_to_end(&mut buffer).map_err(map_timeout)?;
+
+    parse_response(command, buffer)
+}
+
+/// Converts an IO error caused by an elapsed socket timeout into a `YabaiError::Timeout`.
+fn map_timeout(error: io::Error) -> YabaiError {
+    match error.kind() {
+        io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut => YabaiError::Timeout,
+        _ => YabaiError::Io(error),
+    }
+}
+
+/// Splits a message into arguments on spaces.
+pub(crate) fn split_message(message: &str) -> Vec<&str> {
+    message.trim().split(' ').collect()
+}
+
+/// Encodes arguments as a message for the yabai socket.
+///
+/// Each argument is terminated with a NUL byte, followed by a final NUL byte ending the message.
+pub(crate) fn encode_args(args: &[&str]) -> String {
+    format!("{}\0\0", args.join("\0"))
+}
+
+/// Describes a framed message as its space-separated arguments, for naming it in errors.
+pub(crate) fn describe_framed(bytes: &[u8]) -> String {
+    String::from_utf8_lossy(bytes)
+        .split('\0')
+        .filter(|arg| !arg.is_empty())
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
+/// Removes the terminator yabai ends a response with: at most one trailing NUL byte, then at
+/// most one newline before it, in that order. Any other whitespace is kept, so `"2\n\n"`
+/// becomes `"2\n"` and `"\n2"` is unchanged.
+fn trim_response(response: &str) -> &str {
+    let response = response.strip_suffix('\0').unwrap_or(response);
+
+    response.strip_suffix('\n').unwrap_or(response)
+}
+
+/// Interprets a response read from the yabai socket.
+///
+/// yabai closes the connection without writing anything for commands that succeed silently,
+/// and prefixes the response with `0x07` when a command fails. Successful responses have their
+/// trailing NUL byte and newline removed, and are `None` if nothing is left. Bytes that aren't
+/// valid UTF-8, such as in some window titles, are replaced with `U+FFFD` rather than failing
+/// the whole response.
+pub(crate) fn parse_response(command: &str, buffer: Vec<u8>) -> Result<Option<String>, YabaiError> {
+    #[cfg(feature = "log")]
+    log::trace!("received response \"{}\"", buffer.escape_ascii());
+
+    match buffer.split_first() {
+        None => Ok(None),
+        Some((0x07, rest)) => {
+            let error_message = String::from_utf8_lossy(rest).into_owned();
+
+            #[cfg(feature = "log")]
+            log::warn!(
+                "yabai failed to run {:?}: {}",
+                command,
+                error_message.trim_end()
+            );
+
+            let error = YabaiError::CommandError {
+                command: command.to_string(),
+                message: error_message,
+            };
+
+            Err(error)
+        }
+        Some(_) => {
+            let response = String::from_utf8_lossy(&buffer);
+
+            match trim_response(&response) {
+                "" => Ok(None),
+                response => Ok(Some(response.to_string())),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A reader returning at most 7 bytes per read, like a busy socket.
+    struct Trickle<'a>(&'a [u8]);
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = buf.len().min(self.0.len()).min(7);
+            buf[..n].copy_from_slice(&self.0[..n]);
+            self.0 = &self.0[n..];
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn trim_response_removes_one_newline_and_nul() {
+        assert_eq!(trim_response("2\n"), "2");
+        assert_eq!(trim_response("2\0"), "2");
+        assert_eq!(trim_response("2\n\0"), "2");
+        assert_eq!(trim_response("\n"), "");
+        assert_eq!(trim_response("\0"), "");
+    }
+
+    #[test]
+    fn trim_response_keeps_everything_else() {
+        assert_eq!(trim_response("2"), "2");
+        assert_eq!(trim_response("2\n\n"), "2\n");
+        assert_eq!(trim_response("2\0\n"), "2\0");
+        assert_eq!(trim_response("\n2"), "\n2");
+        assert_eq!(trim_response(" 2 "), " 2 ");
+    }
+
+    #[test]
+    fn parse_response_treats_a_bare_newline_as_no_response() {
+        assert_eq!(parse_response("", b"\n".to_vec()).unwrap(), None);
+        assert_eq!(
+            parse_response("", b"2\n".to_vec()).unwrap().as_deref(),
+            Some("2")
+        );
+    }
+
+    #[test]
+    fn read_response_reads_until_the_connection_closes() {
+        let windows = (1..=50)
+            .map(|id| {
+                format!(
+                    r#"{{"id":{id},"app":"App {id}","title":"{}"}}"#,
+                    "x".repeat(200)
+                )
+            })
+            .collect::<Vec<String>>();
+        let json = format!("[{}]", windows.join(","));
+
+        let response = read_response(&mut Trickle(format!("{json}\n").as_bytes())).unwrap();
+
+        assert_eq!(response.as_deref(), Some(json.as_str()));
+    }
+
+    #[test]
+    fn write_args_frames_each_argument() {
+        let mut message = Vec::new();
+        write_args(&mut message, &["window", "--focus", "next"]).unwrap();
+
+        assert_eq!(message, b"\x15\0\0\0window\0--focus\0next\0\0");
+        assert_eq!(describe_framed(&message[4..]), "window --focus next");
+    }
+}
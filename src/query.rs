@@ -0,0 +1,834 @@
+use crate::{
+    protocol::split_message, Command, CycleDirection, DisplayIndex, DisplayInfo, DisplaySelector,
+    QueryKind, RuleInfo, SignalInfo, SpaceIndex, SpaceInfo, SpaceSelector, WindowId, WindowInfo,
+    WindowSelector, Windows, YabaiClient, YabaiError,
+};
+use serde::de::DeserializeOwned;
+use std::{
+    cmp::Reverse,
+    time::{Duration, Instant},
+};
+
+/// An **enum** representing the deserialized response to a `Command::Query`, returned by
+/// `send_query`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum QueryResult {
+    Displays(Vec<DisplayInfo>),
+    Spaces(Vec<SpaceInfo>),
+    Windows(Windows),
+}
+
+/// Queries, which deserialize yabai's JSON responses and require the `serde` feature.
+impl YabaiClient {
+    /// Sends a query to yabai and deserializes the JSON response.
+    ///
+    /// Useful for queries without a dedicated function, or for deserializing into your own types.
+    pub fn query<T: DeserializeOwned>(&self, message: &str) -> Result<T, YabaiError> {
+        self.query_args(&split_message(message))
+    }
+
+    /// Sends a query to yabai as a list of arguments and deserializes the JSON response.
+    fn query_args<T: DeserializeOwned, S: AsRef<str>>(&self, args: &[S]) -> Result<T, YabaiError> {
+        let result = self.send_args(args)?;
+
+        match result {
+            Some(str) => Ok(serde_json::from_str::<T>(&str)?),
+            None => Err(YabaiError::EmptyResponse),
+        }
+    }
+
+    /// Queries yabai for information about all spaces.
+    pub fn query_spaces(&self) -> Result<Vec<SpaceInfo>, YabaiError> {
+        self.query("query --spaces")
+    }
+
+    /// Queries yabai for information about all displays.
+    pub fn query_displays(&self) -> Result<Vec<DisplayInfo>, YabaiError> {
+        self.query("query --displays")
+    }
+
+    /// Queries yabai for information about all windows.
+    pub fn query_windows(&self) -> Result<Windows, YabaiError> {
+        self.query("query --windows")
+    }
+
+    /// Queries yabai for information about all windows, sorted with `WindowInfo::layout_cmp`.
+    pub fn sorted_windows(&self) -> Result<Windows, YabaiError> {
+        let mut windows = self.query_windows()?;
+        windows.sort_by(WindowInfo::layout_cmp);
+
+        Ok(windows)
+    }
+
+    /// Queries yabai for information about all spaces, sorted with `SpaceInfo::display_cmp`.
+    pub fn sorted_spaces(&self) -> Result<Vec<SpaceInfo>, YabaiError> {
+        let mut spaces = self.query_spaces()?;
+        spaces.sort_by(SpaceInfo::display_cmp);
+
+        Ok(spaces)
+    }
+
+    /// Queries yabai for information about the windows shown on all spaces.
+    ///
+    /// Windows can be made sticky with `Command::ToggleWindow { toggle: WindowToggle::Sticky }`.
+    pub fn sticky_windows(&self) -> Result<Windows, YabaiError> {
+        Ok(self.query_windows()?.filter(|window| window.is_sticky))
+    }
+
+    /// Queries yabai for the windows of the app with the given name, ignoring case.
+    pub fn windows_for_app(&self, app: &str) -> Result<Windows, YabaiError> {
+        let app = app.to_lowercase();
+
+        Ok(self
+            .query_windows()?
+            .filter(|window| window.app.to_lowercase() == app))
+    }
+
+    /// Queries yabai for the windows of the app with exactly the given name.
+    pub fn windows_for_app_exact(&self, app: &str) -> Result<Windows, YabaiError> {
+        Ok(self.query_windows()?.by_app(app))
+    }
+
+    /// Queries yabai for the windows of the process with the given id.
+    pub fn windows_for_pid(&self, pid: u32) -> Result<Windows, YabaiError> {
+        Ok(self.query_windows()?.by_pid(pid))
+    }
+
+    /// Waits until a window matching the predicate exists, such as after launching an app.
+    ///
+    /// Windows are polled with `query_windows`, waiting longer between each query up to a
+    /// quarter of a second. Returns a `YabaiError::Timeout` if no window matches before the
+    /// timeout elapses.
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    ///
+    /// let window = yabai::YabaiClient::new()
+    ///     .wait_for_window(|window| window.app == "Safari", Duration::from_secs(5))?;
+    /// # Ok::<(), yabai::YabaiError>(())
+    /// ```
+    pub fn wait_for_window<F: Fn(&WindowInfo) -> bool>(
+        &self,
+        predicate: F,
+        timeout: Duration,
+    ) -> Result<WindowInfo, YabaiError> {
+        let deadline = Instant::now() + timeout;
+        let mut delay = Duration::from_millis(10);
+
+        loop {
+            if let Some(window) = self.query_windows()?.into_iter().find(&predicate) {
+                return Ok(window);
+            }
+
+            let remaining = deadline.saturating_duration_since(Instant::now());
+
+            if remaining.is_zero() {
+                return Err(YabaiError::Timeout);
+            }
+
+            std::thread::sleep(delay.min(remaining));
+            delay = (delay * 2).min(Duration::from_millis(250));
+        }
+    }
+
+    /// Queries yabai for information about the windows on the given space.
+    pub fn query_windows_on_space(&self, space: SpaceIndex) -> Result<Windows, YabaiError> {
+        self.query(&format!("query --windows --space {}", space))
+    }
+
+    /// Queries yabai for information about the windows on the given display.
+    ///
+    /// A display index yabai doesn't know of is returned as a `YabaiError::CommandError`.
+    pub fn query_windows_on_display(&self, display: DisplayIndex) -> Result<Windows, YabaiError> {
+        self.query(&format!("query --windows --display {}", display))
+    }
+
+    /// Queries yabai for information about the spaces on the given display.
+    ///
+    /// A display index yabai doesn't know of is returned as a `YabaiError::CommandError`.
+    pub fn query_spaces_on_display(
+        &self,
+        display: DisplayIndex,
+    ) -> Result<Vec<SpaceInfo>, YabaiError> {
+        self.query(&format!("query --spaces --display {}", display))
+    }
+
+    /// Queries yabai for information about the window with the given id.
+    pub fn query_window(&self, id: WindowId) -> Result<WindowInfo, YabaiError> {
+        self.query(&format!("query --windows --window {}", id))
+    }
+
+    /// Queries yabai for information about the space with the given index.
+    pub fn query_space(&self, index: SpaceIndex) -> Result<SpaceInfo, YabaiError> {
+        self.query(&format!("query --spaces --space {}", index))
+    }
+
+    /// Queries yabai for information about the display with the given index.
+    pub fn query_display(&self, index: DisplayIndex) -> Result<DisplayInfo, YabaiError> {
+        self.query(&format!("query --displays --display {}", index))
+    }
+
+    /// Queries yabai for information about the selected window.
+    pub fn query_window_by_selector(
+        &self,
+        selector: &WindowSelector,
+    ) -> Result<WindowInfo, YabaiError> {
+        self.query_args(&["query", "--windows", "--window", &selector.to_string()])
+    }
+
+    /// Queries yabai for information about the selected space.
+    ///
+    /// The selector is sent as a single argument, so labels are passed through as-is:
+    ///
+    /// ```no_run
+    /// let selector = yabai::SpaceSelector::Label {
+    ///     label: "comms".to_string(),
+    /// };
+    /// let space = yabai::YabaiClient::new().query_space_by_selector(&selector)?;
+    /// # Ok::<(), yabai::YabaiError>(())
+    /// ```
+    pub fn query_space_by_selector(
+        &self,
+        selector: &SpaceSelector,
+    ) -> Result<SpaceInfo, YabaiError> {
+        self.query_args(&["query", "--spaces", "--space", &selector.to_string()])
+    }
+
+    /// Queries yabai for information about the selected display.
+    pub fn query_display_by_selector(
+        &self,
+        selector: &DisplaySelector,
+    ) -> Result<DisplayInfo, YabaiError> {
+        self.query_args(&["query", "--displays", "--display", &selector.to_string()])
+    }
+
+    /// Returns the focused window, if any window has focus.
+    ///
+    /// The focused window is queried directly with `query --windows --window`, which yabai
+    /// answers without listing every window. yabai reports an error for that query when no
+    /// window has focus, and older versions may not support it, so on an error from yabai the
+    /// focused window is looked up among all windows instead.
+    ///
+    /// ```no_run
+    /// if let Some(window) = yabai::YabaiClient::new().focused_window()? {
+    ///     println!("{} is focused", window.app);
+    /// }
+    /// # Ok::<(), yabai::YabaiError>(())
+    /// ```
+    pub fn focused_window(&self) -> Result<Option<WindowInfo>, YabaiError> {
+        match self.send_args(&["query", "--windows", "--window"]) {
+            Ok(Some(window)) => Ok(Some(serde_json::from_str(&window)?)),
+            Ok(None) => Ok(None),
+            Err(YabaiError::CommandError { .. }) => {
+                let windows = self.query_windows()?;
+
+                Ok(windows.into_iter().find(|window| window.has_focus))
+            }
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Returns the topmost visible window containing the given point in screen coordinates,
+    /// such as the mouse position.
+    ///
+    /// Windows on a higher `level` are preferred, then windows with a higher `stack_index`.
+    /// Windows tied on both are ordered by id, preferring the lowest id.
+    pub fn window_at(&self, x: f32, y: f32) -> Result<Option<WindowInfo>, YabaiError> {
+        let windows = self.query_windows()?;
+
+        Ok(windows
+            .into_iter()
+            .filter(|window| window.is_visible && !window.is_minimized)
+            .filter(|window| window.frame.contains_point(x, y))
+            .max_by_key(|window| (window.level, window.stack_index, Reverse(window.id))))
+    }
+
+    /// Returns the focused space, the one yabai reports with `has-focus`.
+    ///
+    /// Returns a `YabaiError::NotFound` if no space has focus.
+    pub fn focused_space(&self) -> Result<SpaceInfo, YabaiError> {
+        let spaces = self.query_spaces()?;
+
+        spaces
+            .into_iter()
+            .find(|space| space.has_focus)
+            .ok_or_else(|| YabaiError::NotFound("no focused space".to_string()))
+    }
+
+    /// Returns the focused space. An alias for `focused_space`.
+    pub fn active_space(&self) -> Result<SpaceInfo, YabaiError> {
+        self.focused_space()
+    }
+
+    /// Returns the space visible on each display, in display order.
+    pub fn visible_spaces(&self) -> Result<Vec<SpaceInfo>, YabaiError> {
+        let mut spaces = self
+            .query_spaces()?
+            .into_iter()
+            .filter(|space| space.is_visible)
+            .collect::<Vec<SpaceInfo>>();
+
+        spaces.sort_by_key(|space| space.display);
+
+        Ok(spaces)
+    }
+
+    /// Focuses the next or previous space by index, wrapping around from the last space to the
+    /// first and from the first to the last.
+    ///
+    /// With `same_display`, only the spaces on the focused display are cycled through,
+    /// otherwise all spaces are. Nothing is sent if the focused space is the only candidate.
+    pub fn focus_space_cyclic(
+        &self,
+        direction: CycleDirection,
+        same_display: bool,
+    ) -> Result<Option<String>, YabaiError> {
+        let spaces = self.query_spaces()?;
+        let focused = spaces
+            .iter()
+            .find(|space| space.has_focus)
+            .ok_or_else(|| YabaiError::NotFound("no focused space".to_string()))?;
+
+        let mut candidates = spaces
+            .iter()
+            .filter(|space| !same_display || space.display == focused.display)
+            .map(|space| space.index)
+            .collect::<Vec<SpaceIndex>>();
+        candidates.sort();
+
+        let position = candidates
+            .iter()
+            .position(|&index| index == focused.index)
+            .unwrap_or(0);
+        let target = match direction {
+            CycleDirection::Next => candidates[(position + 1) % candidates.len()],
+            CycleDirection::Prev => {
+                candidates[(position + candidates.len() - 1) % candidates.len()]
+            }
+        };
+
+        if target == focused.index {
+            return Ok(None);
+        }
+
+        self.focus_space(target)
+    }
+
+    /// Focuses the next window on the focused space, skipping windows that can't be focused
+    /// because they're minimized, hidden or not visible.
+    ///
+    /// Windows are ordered with `WindowInfo::layout_cmp`, wrapping around from the last window
+    /// to the first. If none of the windows has focus, the first window is focused. Returns a
+    /// `YabaiError::NotFound` if the focused space has no window that can be focused.
+    pub fn focus_next_visible_window(&self) -> Result<Option<String>, YabaiError> {
+        let mut windows = self
+            .query::<Windows>("query --windows --space")?
+            .filter(|window| window.is_visible && !window.is_minimized && !window.is_hidden)
+            .into_vec();
+
+        if windows.is_empty() {
+            return Err(YabaiError::NotFound(
+                "no visible windows on the focused space".to_string(),
+            ));
+        }
+
+        windows.sort_by(WindowInfo::layout_cmp);
+
+        let next = match windows.iter().position(|window| window.has_focus) {
+            Some(position) => (position + 1) % windows.len(),
+            None => 0,
+        };
+
+        match &windows[next] {
+            window if window.has_focus => Ok(None),
+            window => self.focus_window(window.id),
+        }
+    }
+
+    /// Sets the opacity of the focused window to `active`, and of the other windows on the
+    /// focused space to `inactive`, such as to dim windows that aren't focused.
+    ///
+    /// Both opacities must be within `0.0..=1.0`, and are validated before any window is
+    /// changed. Windows that can't be moved and windows that aren't standard windows, such as
+    /// system dialogs and panels, are left as they are. The first window yabai fails to change
+    /// is returned as an error.
+    ///
+    /// ```no_run
+    /// yabai::dim_unfocused(1.0, 0.8)?;
+    /// # Ok::<(), yabai::YabaiError>(())
+    /// ```
+    pub fn dim_unfocused(&self, active: f32, inactive: f32) -> Result<(), YabaiError> {
+        for opacity in [active, inactive] {
+            Command::SetWindowOpacity { opacity }.validate()?;
+        }
+
+        let windows = self
+            .query::<Windows>("query --windows --space")?
+            .filter(|window| window.can_move && window.is_standard_window());
+
+        for window in windows {
+            let opacity = if window.has_focus { active } else { inactive };
+
+            self.send_command(&Command::SetSelectedWindowOpacity {
+                window: WindowSelector::Id { id: window.id },
+                opacity,
+            })?;
+        }
+
+        Ok(())
+    }
+
+    /// Moves the spaces with the given labels so they're next to each other in the given order,
+    /// starting at the position of the first of them.
+    ///
+    /// Spaces already in position aren't moved, so arranging spaces that are already in order
+    /// sends nothing. Labels without a space are returned together as a `YabaiError::NotFound`
+    /// before any space is moved. yabai only moves spaces within a display, so the spaces
+    /// should all be on the same display.
+    ///
+    /// ```no_run
+    /// yabai::arrange_spaces(&["code", "web", "comms"])?;
+    /// # Ok::<(), yabai::YabaiError>(())
+    /// ```
+    pub fn arrange_spaces(&self, order: &[&str]) -> Result<(), YabaiError> {
+        let mut spaces = self.query_spaces()?;
+        spaces.sort_by_key(|space| space.index);
+
+        let first_index = spaces.first().map_or(1, |space| space.index.0);
+        let mut labels = spaces
+            .into_iter()
+            .map(|space| space.label)
+            .collect::<Vec<String>>();
+
+        let missing = order
+            .iter()
+            .filter(|label| !labels.iter().any(|other| other == *label))
+            .copied()
+            .collect::<Vec<&str>>();
+
+        if !missing.is_empty() {
+            return Err(YabaiError::NotFound(format!(
+                "no spaces labelled {}",
+                missing.join(", ")
+            )));
+        }
+
+        for (position, label) in order.iter().enumerate() {
+            if order[..position].contains(label) {
+                return Err(YabaiError::InvalidArgument(format!(
+                    "space label {} is given more than once",
+                    label
+                )));
+            }
+        }
+
+        let start = order
+            .iter()
+            .filter_map(|label| labels.iter().position(|other| other == label))
+            .min()
+            .unwrap_or(0);
+
+        for (offset, label) in order.iter().enumerate() {
+            let target = start + offset;
+            let Some(current) = labels.iter().position(|other| other == label) else {
+                continue;
+            };
+
+            if current != target {
+                let moved = labels.remove(current);
+                labels.insert(target, moved);
+
+                self.send_command(&Command::MoveSelectedSpace {
+                    space: SpaceSelector::Label {
+                        label: label.to_string(),
+                    },
+                    to: SpaceIndex(first_index + target as u32),
+                })?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the focused display.
+    ///
+    /// yabai reports the focused display when `--display` is given without a selector.
+    pub fn focused_display(&self) -> Result<DisplayInfo, YabaiError> {
+        self.query("query --displays --display")
+    }
+
+    /// Sends a `Command::Query` for the given kind of entity, deserializing the response into
+    /// the matching `QueryResult`.
+    ///
+    /// ```no_run
+    /// use yabai::{QueryKind, QueryResult};
+    ///
+    /// let client = yabai::YabaiClient::new();
+    ///
+    /// if let QueryResult::Windows(windows) = client.send_query(QueryKind::Windows)? {
+    ///     println!("{} windows", windows.len());
+    /// }
+    /// # Ok::<(), yabai::YabaiError>(())
+    /// ```
+    pub fn send_query(&self, kind: QueryKind) -> Result<QueryResult, YabaiError> {
+        let response = self
+            .send_command(&Command::Query { kind })?
+            .ok_or(YabaiError::EmptyResponse)?;
+
+        Ok(match kind {
+            QueryKind::Displays => QueryResult::Displays(serde_json::from_str(&response)?),
+            QueryKind::Spaces => QueryResult::Spaces(serde_json::from_str(&response)?),
+            QueryKind::Windows => QueryResult::Windows(serde_json::from_str(&response)?),
+        })
+    }
+
+    /// Queries yabai for information about all signals.
+    pub fn query_signals(&self) -> Result<Vec<SignalInfo>, YabaiError> {
+        self.query("signal --list")
+    }
+
+    /// Queries yabai for information about all rules.
+    pub fn query_rules(&self) -> Result<Vec<RuleInfo>, YabaiError> {
+        self.query("rule --list")
+    }
+}
+
+/// Sends a query to yabai and deserializes the JSON response.
+///
+/// Useful for queries without a dedicated function, or for deserializing into your own types:
+///
+/// ```no_run
+/// let spaces: Vec<yabai::SpaceInfo> = yabai::query("query --spaces --display 2")?;
+/// # Ok::<(), yabai::YabaiError>(())
+/// ```
+pub fn query<T: DeserializeOwned>(message: &str) -> Result<T, YabaiError> {
+    YabaiClient::new().query(message)
+}
+
+/// Sends a `Command::Query` for the given kind of entity, deserializing the response into
+/// the matching `QueryResult`.
+pub fn send_query(kind: QueryKind) -> Result<QueryResult, YabaiError> {
+    YabaiClient::new().send_query(kind)
+}
+
+/// Queries yabai for information about all spaces.
+pub fn query_spaces() -> Result<Vec<SpaceInfo>, YabaiError> {
+    YabaiClient::new().query_spaces()
+}
+
+/// Queries yabai for information about all displays.
+pub fn query_displays() -> Result<Vec<DisplayInfo>, YabaiError> {
+    YabaiClient::new().query_displays()
+}
+
+/// Queries yabai for information about all windows.
+pub fn query_windows() -> Result<Windows, YabaiError> {
+    YabaiClient::new().query_windows()
+}
+
+/// Queries yabai for information about all windows, sorted by space and position.
+pub fn sorted_windows() -> Result<Windows, YabaiError> {
+    YabaiClient::new().sorted_windows()
+}
+
+/// Queries yabai for information about all spaces, sorted by display and index.
+pub fn sorted_spaces() -> Result<Vec<SpaceInfo>, YabaiError> {
+    YabaiClient::new().sorted_spaces()
+}
+
+/// Queries yabai for information about the windows shown on all spaces.
+pub fn sticky_windows() -> Result<Windows, YabaiError> {
+    YabaiClient::new().sticky_windows()
+}
+
+/// Waits until a window matching the predicate exists, returning a `YabaiError::Timeout` if
+/// none does before the timeout elapses.
+///
+/// See `YabaiClient::wait_for_window` for details.
+pub fn wait_for_window<F: Fn(&WindowInfo) -> bool>(
+    predicate: F,
+    timeout: Duration,
+) -> Result<WindowInfo, YabaiError> {
+    YabaiClient::new().wait_for_window(predicate, timeout)
+}
+
+/// Queries yabai for the windows of the app with the given name, ignoring case.
+pub fn windows_for_app(app: &str) -> Result<Windows, YabaiError> {
+    YabaiClient::new().windows_for_app(app)
+}
+
+/// Queries yabai for the windows of the app with exactly the given name.
+pub fn windows_for_app_exact(app: &str) -> Result<Windows, YabaiError> {
+    YabaiClient::new().windows_for_app_exact(app)
+}
+
+/// Queries yabai for the windows of the process with the given id.
+pub fn windows_for_pid(pid: u32) -> Result<Windows, YabaiError> {
+    YabaiClient::new().windows_for_pid(pid)
+}
+
+/// Queries yabai for information about the windows on the given space.
+pub fn query_windows_on_space(space: SpaceIndex) -> Result<Windows, YabaiError> {
+    YabaiClient::new().query_windows_on_space(space)
+}
+
+/// Queries yabai for information about the windows on the given display.
+pub fn query_windows_on_display(display: DisplayIndex) -> Result<Windows, YabaiError> {
+    YabaiClient::new().query_windows_on_display(display)
+}
+
+/// Queries yabai for information about the spaces on the given display.
+pub fn query_spaces_on_display(display: DisplayIndex) -> Result<Vec<SpaceInfo>, YabaiError> {
+    YabaiClient::new().query_spaces_on_display(display)
+}
+
+/// Queries yabai for information about the window with the given id.
+///
+/// An unknown id results in a `YabaiError::CommandError`.
+pub fn query_window(id: WindowId) -> Result<WindowInfo, YabaiError> {
+    YabaiClient::new().query_window(id)
+}
+
+/// Queries yabai for information about the space with the given index.
+///
+/// An unknown index results in a `YabaiError::CommandError`.
+pub fn query_space(index: SpaceIndex) -> Result<SpaceInfo, YabaiError> {
+    YabaiClient::new().query_space(index)
+}
+
+/// Queries yabai for information about the display with the given index.
+///
+/// An unknown index results in a `YabaiError::CommandError`.
+pub fn query_display(index: DisplayIndex) -> Result<DisplayInfo, YabaiError> {
+    YabaiClient::new().query_display(index)
+}
+
+/// Queries yabai for information about the selected window.
+pub fn query_window_by_selector(selector: &WindowSelector) -> Result<WindowInfo, YabaiError> {
+    YabaiClient::new().query_window_by_selector(selector)
+}
+
+/// Queries yabai for information about the selected space, such as by label.
+pub fn query_space_by_selector(selector: &SpaceSelector) -> Result<SpaceInfo, YabaiError> {
+    YabaiClient::new().query_space_by_selector(selector)
+}
+
+/// Queries yabai for information about the selected display.
+pub fn query_display_by_selector(selector: &DisplaySelector) -> Result<DisplayInfo, YabaiError> {
+    YabaiClient::new().query_display_by_selector(selector)
+}
+
+/// Returns the focused window, or `None` if no window has focus.
+pub fn focused_window() -> Result<Option<WindowInfo>, YabaiError> {
+    YabaiClient::new().focused_window()
+}
+
+/// Returns the topmost visible window containing the given point in screen coordinates.
+///
+/// See `YabaiClient::window_at` for how overlapping windows are ordered.
+pub fn window_at(x: f32, y: f32) -> Result<Option<WindowInfo>, YabaiError> {
+    YabaiClient::new().window_at(x, y)
+}
+
+/// Returns the focused space.
+pub fn focused_space() -> Result<SpaceInfo, YabaiError> {
+    YabaiClient::new().focused_space()
+}
+
+/// Returns the focused space. An alias for `focused_space`.
+pub fn active_space() -> Result<SpaceInfo, YabaiError> {
+    YabaiClient::new().active_space()
+}
+
+/// Returns the space visible on each display, in display order.
+pub fn visible_spaces() -> Result<Vec<SpaceInfo>, YabaiError> {
+    YabaiClient::new().visible_spaces()
+}
+
+/// Focuses the next or previous space by index, wrapping around at either end.
+///
+/// See `YabaiClient::focus_space_cyclic` for how spaces are selected.
+pub fn focus_space_cyclic(
+    direction: CycleDirection,
+    same_display: bool,
+) -> Result<Option<String>, YabaiError> {
+    YabaiClient::new().focus_space_cyclic(direction, same_display)
+}
+
+/// Focuses the next window on the focused space, skipping windows that can't be focused.
+///
+/// See `YabaiClient::focus_next_visible_window` for how windows are ordered.
+pub fn focus_next_visible_window() -> Result<Option<String>, YabaiError> {
+    YabaiClient::new().focus_next_visible_window()
+}
+
+/// Sets the opacity of the focused window to `active`, and of the other windows on the
+/// focused space to `inactive`.
+///
+/// See `YabaiClient::dim_unfocused` for which windows are changed.
+pub fn dim_unfocused(active: f32, inactive: f32) -> Result<(), YabaiError> {
+    YabaiClient::new().dim_unfocused(active, inactive)
+}
+
+/// Moves the spaces with the given labels so they're next to each other in the given order.
+///
+/// See `YabaiClient::arrange_spaces` for how spaces are moved.
+pub fn arrange_spaces(order: &[&str]) -> Result<(), YabaiError> {
+    YabaiClient::new().arrange_spaces(order)
+}
+
+/// Returns the focused display.
+pub fn focused_display() -> Result<DisplayInfo, YabaiError> {
+    YabaiClient::new().focused_display()
+}
+
+/// Queries yabai for information about all signals.
+///
+/// yabai lists signals with `signal --list`, rather than through `query`.
+pub fn query_signals() -> Result<Vec<SignalInfo>, YabaiError> {
+    YabaiClient::new().query_signals()
+}
+
+/// Queries yabai for information about all rules.
+///
+/// yabai lists rules with `rule --list`, rather than through `query`.
+pub fn query_rules() -> Result<Vec<RuleInfo>, YabaiError> {
+    YabaiClient::new().query_rules()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockYabai;
+
+    #[test]
+    fn focused_window_falls_back_to_listing_windows() {
+        let mock =
+            MockYabai::start(&[b"\x07could not retrieve window details.\n", b"[]\n"]).unwrap();
+
+        assert_eq!(mock.client().focused_window().unwrap(), None);
+        assert_eq!(
+            mock.finish().unwrap(),
+            ["query --windows --window", "query --windows"]
+        );
+    }
+
+    /// Formats spaces with the given labels, on one display and indexed in order, as yabai
+    /// reports them.
+    fn spaces_json(labels: &[&str]) -> Vec<u8> {
+        let spaces = labels
+            .iter()
+            .zip(1..)
+            .map(|(label, index)| {
+                format!(
+                    r#"{{"id":{index},"uuid":"","index":{index},"label":"{label}","type":"bsp","display":1,"windows":[],"first-window":0,"last-window":0,"has-focus":false,"is-visible":false,"is-native-fullscreen":false}}"#
+                )
+            })
+            .collect::<Vec<String>>();
+
+        format!("[{}]\n", spaces.join(",")).into_bytes()
+    }
+
+    #[test]
+    fn arrange_spaces_sends_the_fewest_moves() {
+        let spaces = spaces_json(&["a", "b", "c", "d"]);
+        let mock = MockYabai::start(&[&spaces, b"", b"", b""]).unwrap();
+
+        mock.client().arrange_spaces(&["d", "c", "b", "a"]).unwrap();
+
+        assert_eq!(
+            mock.finish().unwrap(),
+            [
+                "query --spaces",
+                "space d --move 1",
+                "space c --move 2",
+                "space b --move 3",
+            ]
+        );
+    }
+
+    #[test]
+    fn arrange_spaces_starts_at_the_first_arranged_space() {
+        let spaces = spaces_json(&["web", "mail", "code", "comms"]);
+        let mock = MockYabai::start(&[&spaces, b""]).unwrap();
+
+        mock.client().arrange_spaces(&["comms", "code"]).unwrap();
+
+        assert_eq!(
+            mock.finish().unwrap(),
+            ["query --spaces", "space comms --move 3"]
+        );
+    }
+
+    #[test]
+    fn arrange_spaces_in_order_sends_nothing() {
+        let spaces = spaces_json(&["web", "code"]);
+        let mock = MockYabai::start(&[&spaces]).unwrap();
+
+        mock.client().arrange_spaces(&["web", "code"]).unwrap();
+
+        assert_eq!(mock.finish().unwrap(), ["query --spaces"]);
+    }
+
+    #[test]
+    fn arrange_spaces_lists_every_missing_label() {
+        let spaces = spaces_json(&["web"]);
+        let mock = MockYabai::start(&[&spaces]).unwrap();
+
+        assert!(matches!(
+            mock.client().arrange_spaces(&["mail", "web", "code"]),
+            Err(YabaiError::NotFound(message)) if message == "no spaces labelled mail, code"
+        ));
+        assert_eq!(mock.finish().unwrap(), ["query --spaces"]);
+    }
+
+    #[test]
+    fn dim_unfocused_sets_the_opacity_of_each_standard_window() {
+        let window = |id, focus, can_move, subrole| {
+            format!(
+                r#"{{"id":{id},"pid":1,"app":"App","title":"","frame":{{"x":0,"y":0,"w":1,"h":1}},"role":"AXWindow","subrole":"{subrole}","display":1,"space":1,"has-focus":{focus},"can-move":{can_move}}}"#
+            )
+        };
+        let windows = format!(
+            "[{},{},{},{}]\n",
+            window(1, false, true, "AXStandardWindow"),
+            window(2, true, true, "AXStandardWindow"),
+            window(3, false, false, "AXStandardWindow"),
+            window(4, false, true, "AXDialog"),
+        );
+        let mock = MockYabai::start(&[windows.as_bytes(), b"", b""]).unwrap();
+
+        mock.client().dim_unfocused(1.0, 0.8).unwrap();
+
+        assert_eq!(
+            mock.finish().unwrap(),
+            [
+                "query --windows --space",
+                "window 1 --opacity 0.8",
+                "window 2 --opacity 1",
+            ]
+        );
+    }
+
+    #[test]
+    fn dim_unfocused_validates_before_querying() {
+        let client = YabaiClient::dry_run();
+
+        assert!(matches!(
+            client.dim_unfocused(1.0, 1.2),
+            Err(YabaiError::InvalidArgument(_))
+        ));
+        assert!(client.recorded_messages().is_empty());
+    }
+
+    #[test]
+    fn send_query_deserializes_the_matching_result() {
+        let mock = MockYabai::start(&[b"[]\n"]).unwrap();
+
+        assert!(matches!(
+            mock.client().send_query(QueryKind::Windows).unwrap(),
+            QueryResult::Windows(windows) if windows.is_empty()
+        ));
+        assert_eq!(mock.finish().unwrap(), ["query --windows"]);
+    }
+}
@@ -0,0 +1,157 @@
+use crate::{config::on_off, DisplaySelector, SpaceSelector};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+/// A window rule, as added with the `rule --add` command.
+///
+/// Built up from `Rule::new`, with each property only sent to yabai if it was set. Each
+/// property is sent as a single `key=value` argument, so regexes and values containing
+/// spaces don't need any quoting or escaping:
+///
+/// ```
+/// let rule = yabai::Rule::new()
+///     .label("settings")
+///     .app("^System Settings$")
+///     .title("(General|About)")
+///     .manage(false);
+///
+/// assert_eq!(
+///     rule.to_args(),
+///     vec![
+///         "label=settings",
+///         "app=^System Settings$",
+///         "title=(General|About)",
+///         "manage=off",
+///     ]
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "python", pyclass)]
+pub struct Rule {
+    pub label: Option<String>,
+    pub app: Option<String>,
+    pub title: Option<String>,
+    pub space: Option<SpaceSelector>,
+    pub display: Option<DisplaySelector>,
+    pub opacity: Option<f32>,
+    pub manage: Option<bool>,
+    pub sticky: Option<bool>,
+}
+
+impl Rule {
+    /// Creates a rule with no properties set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the label used to identify the rule, such as when removing it.
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Sets the regex the application name must match.
+    pub fn app(mut self, app: impl Into<String>) -> Self {
+        self.app = Some(app.into());
+        self
+    }
+
+    /// Sets the regex the window title must match.
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Sets the space matching windows are moved to.
+    pub fn space(mut self, space: SpaceSelector) -> Self {
+        self.space = Some(space);
+        self
+    }
+
+    /// Sets the display matching windows are moved to.
+    pub fn display(mut self, display: DisplaySelector) -> Self {
+        self.display = Some(display);
+        self
+    }
+
+    /// Sets the opacity of matching windows, within `0.0..=1.0`.
+    pub fn opacity(mut self, opacity: f32) -> Self {
+        self.opacity = Some(opacity);
+        self
+    }
+
+    /// Sets whether yabai manages the layout of matching windows.
+    pub fn manage(mut self, manage: bool) -> Self {
+        self.manage = Some(manage);
+        self
+    }
+
+    /// Sets whether matching windows appear on all spaces.
+    pub fn sticky(mut self, sticky: bool) -> Self {
+        self.sticky = Some(sticky);
+        self
+    }
+
+    /// Returns the `key=value` arguments for the properties that are set.
+    pub fn to_args(&self) -> Vec<String> {
+        let properties = [
+            ("label", self.label.clone()),
+            ("app", self.app.clone()),
+            ("title", self.title.clone()),
+            ("space", self.space.as_ref().map(ToString::to_string)),
+            ("display", self.display.as_ref().map(ToString::to_string)),
+            ("opacity", self.opacity.map(|opacity| opacity.to_string())),
+            (
+                "manage",
+                self.manage.map(|manage| on_off(manage).to_string()),
+            ),
+            (
+                "sticky",
+                self.sticky.map(|sticky| on_off(sticky).to_string()),
+            ),
+        ];
+
+        properties
+            .into_iter()
+            .filter_map(|(key, value)| value.map(|value| format!("{key}={value}")))
+            .collect()
+    }
+}
+
+/// Information about a rule, as listed by `rule --list`.
+///
+/// Properties that weren't set on the rule are reported with their default values.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "python", pyclass)]
+pub struct RuleInfo {
+    pub index: u32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub label: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub app: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub title: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub role: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub subrole: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub display: u32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub space: u32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub opacity: f32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub manage: Option<bool>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub sticky: Option<bool>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub grid: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub scratchpad: String,
+}
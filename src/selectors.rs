@@ -0,0 +1,253 @@
+use crate::{Direction, DisplayIndex, SpaceIndex, WindowId, YabaiError};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use strum_macros::Display;
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+/// An **enum** representing a yabai window selector.
+///
+/// Formats as the token yabai expects, and can be parsed back from it:
+///
+/// ```
+/// let selector: yabai::WindowSelector = "stack.next".parse()?;
+/// assert_eq!(selector, yabai::WindowSelector::StackNext {});
+/// assert_eq!(yabai::WindowSelector::Id { id: 42.into() }.to_string(), "42");
+/// # Ok::<(), yabai::YabaiError>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "python", pyclass)]
+pub enum WindowSelector {
+    #[strum(serialize = "prev")]
+    Prev {},
+    #[strum(serialize = "next")]
+    Next {},
+    #[strum(serialize = "first")]
+    First {},
+    #[strum(serialize = "last")]
+    Last {},
+    #[strum(serialize = "recent")]
+    Recent {},
+    #[strum(serialize = "mouse")]
+    Mouse {},
+    #[strum(serialize = "largest")]
+    Largest {},
+    #[strum(serialize = "smallest")]
+    Smallest {},
+    #[strum(serialize = "sibling")]
+    Sibling {},
+    #[strum(serialize = "first_nephew")]
+    FirstNephew {},
+    #[strum(serialize = "second_nephew")]
+    SecondNephew {},
+    #[strum(serialize = "uncle")]
+    Uncle {},
+    #[strum(serialize = "first_cousin")]
+    FirstCousin {},
+    #[strum(serialize = "second_cousin")]
+    SecondCousin {},
+    #[strum(serialize = "stack.prev")]
+    StackPrev {},
+    #[strum(serialize = "stack.next")]
+    StackNext {},
+    #[strum(serialize = "stack.first")]
+    StackFirst {},
+    #[strum(serialize = "stack.last")]
+    StackLast {},
+    #[strum(serialize = "stack.recent")]
+    StackRecent {},
+    #[strum(to_string = "{direction}")]
+    Direction { direction: Direction },
+    #[strum(to_string = "{id}")]
+    Id { id: WindowId },
+}
+
+/// An **enum** representing a yabai space selector.
+///
+/// Formats as the token yabai expects. Any token that isn't a named selector or an index
+/// parses as a label:
+///
+/// ```
+/// let selector: yabai::SpaceSelector = "comms".parse()?;
+/// assert_eq!(selector.to_string(), "comms");
+///
+/// let selector: yabai::SpaceSelector = "3".parse()?;
+/// assert_eq!(selector, yabai::SpaceSelector::Index { index: 3.into() });
+/// # Ok::<(), yabai::YabaiError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Display)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "python", pyclass)]
+pub enum SpaceSelector {
+    #[strum(serialize = "prev")]
+    Prev {},
+    #[strum(serialize = "next")]
+    Next {},
+    #[strum(serialize = "first")]
+    First {},
+    #[strum(serialize = "last")]
+    Last {},
+    #[strum(serialize = "recent")]
+    Recent {},
+    #[strum(serialize = "mouse")]
+    Mouse {},
+    #[strum(to_string = "{index}")]
+    Index { index: SpaceIndex },
+    #[strum(to_string = "{label}")]
+    Label { label: String },
+}
+
+/// An **enum** representing a yabai display selector.
+///
+/// Formats as the token yabai expects. Any token that isn't a named selector, a direction,
+/// or an index parses as a label.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Display)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "python", pyclass)]
+pub enum DisplaySelector {
+    #[strum(serialize = "prev")]
+    Prev {},
+    #[strum(serialize = "next")]
+    Next {},
+    #[strum(serialize = "first")]
+    First {},
+    #[strum(serialize = "last")]
+    Last {},
+    #[strum(serialize = "recent")]
+    Recent {},
+    #[strum(serialize = "mouse")]
+    Mouse {},
+    #[strum(to_string = "{direction}")]
+    Direction { direction: Direction },
+    #[strum(to_string = "{index}")]
+    Index { index: DisplayIndex },
+    #[strum(to_string = "{label}")]
+    Label { label: String },
+}
+
+fn parse_direction(token: &str) -> Option<Direction> {
+    match token {
+        "north" => Some(Direction::North),
+        "south" => Some(Direction::South),
+        "east" => Some(Direction::East),
+        "west" => Some(Direction::West),
+        _ => None,
+    }
+}
+
+impl FromStr for WindowSelector {
+    type Err = YabaiError;
+
+    fn from_str(token: &str) -> Result<Self, Self::Err> {
+        let selector = match token {
+            "prev" => WindowSelector::Prev {},
+            "next" => WindowSelector::Next {},
+            "first" => WindowSelector::First {},
+            "last" => WindowSelector::Last {},
+            "recent" => WindowSelector::Recent {},
+            "mouse" => WindowSelector::Mouse {},
+            "largest" => WindowSelector::Largest {},
+            "smallest" => WindowSelector::Smallest {},
+            "sibling" => WindowSelector::Sibling {},
+            "first_nephew" => WindowSelector::FirstNephew {},
+            "second_nephew" => WindowSelector::SecondNephew {},
+            "uncle" => WindowSelector::Uncle {},
+            "first_cousin" => WindowSelector::FirstCousin {},
+            "second_cousin" => WindowSelector::SecondCousin {},
+            "stack.prev" => WindowSelector::StackPrev {},
+            "stack.next" => WindowSelector::StackNext {},
+            "stack.first" => WindowSelector::StackFirst {},
+            "stack.last" => WindowSelector::StackLast {},
+            "stack.recent" => WindowSelector::StackRecent {},
+            token => match (parse_direction(token), token.parse::<u32>()) {
+                (Some(direction), _) => WindowSelector::Direction { direction },
+                (None, Ok(id)) => WindowSelector::Id { id: id.into() },
+                (None, Err(_)) => {
+                    return Err(YabaiError::InvalidArgument(format!(
+                        "unknown window selector {token:?}"
+                    )))
+                }
+            },
+        };
+
+        Ok(selector)
+    }
+}
+
+impl FromStr for SpaceSelector {
+    type Err = YabaiError;
+
+    fn from_str(token: &str) -> Result<Self, Self::Err> {
+        let selector = match token {
+            "prev" => SpaceSelector::Prev {},
+            "next" => SpaceSelector::Next {},
+            "first" => SpaceSelector::First {},
+            "last" => SpaceSelector::Last {},
+            "recent" => SpaceSelector::Recent {},
+            "mouse" => SpaceSelector::Mouse {},
+            token => match token.parse::<u32>() {
+                Ok(index) => SpaceSelector::Index {
+                    index: index.into(),
+                },
+                Err(_) => SpaceSelector::Label {
+                    label: token.to_string(),
+                },
+            },
+        };
+
+        Ok(selector)
+    }
+}
+
+impl FromStr for DisplaySelector {
+    type Err = YabaiError;
+
+    fn from_str(token: &str) -> Result<Self, Self::Err> {
+        let selector = match token {
+            "prev" => DisplaySelector::Prev {},
+            "next" => DisplaySelector::Next {},
+            "first" => DisplaySelector::First {},
+            "last" => DisplaySelector::Last {},
+            "recent" => DisplaySelector::Recent {},
+            "mouse" => DisplaySelector::Mouse {},
+            token => match (parse_direction(token), token.parse::<u32>()) {
+                (Some(direction), _) => DisplaySelector::Direction { direction },
+                (None, Ok(index)) => DisplaySelector::Index {
+                    index: index.into(),
+                },
+                (None, Err(_)) => DisplaySelector::Label {
+                    label: token.to_string(),
+                },
+            },
+        };
+
+        Ok(selector)
+    }
+}
+
+impl From<WindowId> for WindowSelector {
+    fn from(id: WindowId) -> Self {
+        WindowSelector::Id { id }
+    }
+}
+
+impl From<Direction> for WindowSelector {
+    fn from(direction: Direction) -> Self {
+        WindowSelector::Direction { direction }
+    }
+}
+
+impl From<SpaceIndex> for SpaceSelector {
+    fn from(index: SpaceIndex) -> Self {
+        SpaceSelector::Index { index }
+    }
+}
+
+impl From<DisplayIndex> for DisplaySelector {
+    fn from(index: DisplayIndex) -> Self {
+        DisplaySelector::Index { index }
+    }
+}
@@ -0,0 +1,64 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumString};
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+/// An **enum** representing an event that a yabai signal can be triggered by.
+///
+/// See the [yabai documentation](https://github.com/koekeishiya/yabai/wiki/Commands#automation-with-rules-and-signals) for the variables yabai passes to each event's action.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[strum(serialize_all = "snake_case")]
+#[cfg_attr(feature = "python", pyclass)]
+pub enum Event {
+    ApplicationLaunched,
+    ApplicationTerminated,
+    ApplicationFrontSwitched,
+    ApplicationActivated,
+    ApplicationDeactivated,
+    ApplicationVisible,
+    ApplicationHidden,
+    WindowCreated,
+    WindowDestroyed,
+    WindowFocused,
+    WindowMoved,
+    WindowResized,
+    WindowMinimized,
+    WindowDeminimized,
+    WindowTitleChanged,
+    SpaceCreated,
+    SpaceDestroyed,
+    SpaceChanged,
+    DisplayAdded,
+    DisplayRemoved,
+    DisplayMoved,
+    DisplayResized,
+    DisplayChanged,
+    MissionControlEnter,
+    MissionControlExit,
+    DockDidRestart,
+    MenuBarHiddenChanged,
+    DockDidChangePref,
+    SystemWoke,
+}
+
+/// Information about a signal, as listed by `signal --list`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "python", pyclass)]
+pub struct SignalInfo {
+    pub index: u32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub label: String,
+    /// The regex the application name must match for the signal to trigger, if any.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub app: String,
+    /// The regex the window title must match for the signal to trigger, if any.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub title: String,
+    pub event: String,
+    pub action: String,
+}
@@ -0,0 +1,245 @@
+use crate::{Command, Event, YabaiClient, YabaiError};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    io::{BufRead, BufReader},
+    os::unix::net::UnixListener,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// Counts subscriptions made by this process, so each gets its own socket and signal labels.
+static SUBSCRIPTION_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+/// The byte a signal action writes after each event's payload.
+const EVENT_TERMINATOR: u8 = b'\0';
+
+/// An event received from yabai through a `Subscription`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct YabaiEvent {
+    /// The event that triggered the signal.
+    pub event: Event,
+    /// The `YABAI_*` variables yabai passed to the signal, such as `YABAI_WINDOW_ID`.
+    pub variables: HashMap<String, String>,
+}
+
+impl YabaiEvent {
+    /// Returns the value of a `YABAI_*` variable, if yabai passed it for this event.
+    pub fn variable(&self, name: &str) -> Option<&str> {
+        self.variables.get(name).map(String::as_str)
+    }
+
+    /// Parses the payload written by a subscription's signal action: the event name on the
+    /// first line, followed by a `NAME=value` line for each variable.
+    fn parse(payload: &str) -> Option<Self> {
+        let mut lines = payload.lines();
+        let event = lines.next()?.trim().parse().ok()?;
+
+        let variables = lines
+            .filter_map(|line| line.split_once('='))
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+
+        Some(YabaiEvent { event, variables })
+    }
+}
+
+/// A subscription to yabai events, yielding each event as yabai reports it.
+///
+/// For each event, a signal is added whose action writes the event and its `YABAI_*`
+/// variables to a socket owned by the subscription. yabai runs the action with `sh`, and it
+/// connects to the socket with `nc -U`, so `nc` must be on yabai's `PATH` for events to arrive.
+/// Each payload ends with a NUL byte, which is read up to rather than waiting for `nc` to
+/// close the connection, as the BSD `nc` shipped with macOS doesn't when its input ends.
+///
+/// Iterating blocks until the next event arrives. The signals are removed and the socket is
+/// deleted when the subscription is dropped.
+///
+/// Malformed payloads, including connections closed before the terminator, are skipped, and
+/// iteration ends if the socket can no longer accept connections.
+#[derive(Debug)]
+pub struct Subscription {
+    client: YabaiClient,
+    listener: UnixListener,
+    path: PathBuf,
+    labels: Vec<String>,
+}
+
+impl Subscription {
+    /// Returns the path of the socket events are written to.
+    pub fn socket_path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Iterator for Subscription {
+    type Item = YabaiEvent;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (stream, _) = self.listener.accept().ok()?;
+            let mut payload = Vec::new();
+
+            match BufReader::new(stream).read_until(EVENT_TERMINATOR, &mut payload) {
+                Ok(_) if payload.pop() == Some(EVENT_TERMINATOR) => {}
+                _ => continue,
+            }
+
+            if let Some(event) = YabaiEvent::parse(&String::from_utf8_lossy(&payload)) {
+                return Some(event);
+            }
+        }
+    }
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        for label in self.labels.drain(..) {
+            let _ = self.client.send_command(&Command::RemoveSignal { label });
+        }
+
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+impl YabaiClient {
+    /// Subscribes to the given events, returning an iterator over them as yabai reports them.
+    ///
+    /// See `Subscription` for how events are received.
+    ///
+    /// ```no_run
+    /// use yabai::Event;
+    ///
+    /// let client = yabai::YabaiClient::new();
+    ///
+    /// for event in client.subscribe(&[Event::WindowFocused])? {
+    ///     println!("focused window {:?}", event.variable("YABAI_WINDOW_ID"));
+    /// }
+    /// # Ok::<(), yabai::YabaiError>(())
+    /// ```
+    pub fn subscribe(&self, events: &[Event]) -> Result<Subscription, YabaiError> {
+        let id = format!(
+            "yabai-rs-{}-{}",
+            std::process::id(),
+            SUBSCRIPTION_COUNT.fetch_add(1, Ordering::Relaxed)
+        );
+        let path = std::env::temp_dir().join(format!("{id}.socket"));
+
+        let _ = std::fs::remove_file(&path);
+        let listener = UnixListener::bind(&path)?;
+
+        let mut subscription = Subscription {
+            client: self.clone(),
+            listener,
+            path,
+            labels: Vec::new(),
+        };
+
+        for event in events {
+            let label = format!("{id}-{event}");
+
+            self.send_command(&Command::AddSignal {
+                event: *event,
+                action: signal_action(*event, &subscription.path),
+                label: Some(label.clone()),
+            })?;
+
+            subscription.labels.push(label);
+        }
+
+        Ok(subscription)
+    }
+}
+
+/// Returns the shell command a subscription's signal runs to write an event to the socket at
+/// `path`, ending the payload with `EVENT_TERMINATOR`.
+fn signal_action(event: Event, path: &Path) -> String {
+    let target = shell_quote(&path.to_string_lossy());
+
+    format!("{{ echo {event}; env | grep '^YABAI_'; printf '\\0'; }} | nc -U {target}")
+}
+
+/// Quotes a string as a single shell word.
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockYabai;
+    use std::{io::Write, os::unix::net::UnixStream, process::Command as Process};
+
+    #[test]
+    fn subscribe_yields_events_and_removes_its_signals() {
+        let mock = MockYabai::start(&[b"", b""]).unwrap();
+        let mut events = mock.client().subscribe(&[Event::WindowFocused]).unwrap();
+
+        UnixStream::connect(events.socket_path())
+            .unwrap()
+            .write_all(b"window_focused\nYABAI_WINDOW_ID=42\n\0")
+            .unwrap();
+
+        let event = events.next().unwrap();
+        assert_eq!(event.event, Event::WindowFocused);
+        assert_eq!(event.variable("YABAI_WINDOW_ID"), Some("42"));
+
+        let path = events.socket_path().to_path_buf();
+        drop(events);
+
+        let messages = mock.finish().unwrap();
+        assert!(messages[0].starts_with("signal --add event=window_focused"));
+        assert!(messages[1].starts_with("signal --remove yabai-rs-"));
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn payloads_closed_before_the_terminator_are_skipped() {
+        let mut events = YabaiClient::dry_run()
+            .subscribe(&[Event::WindowFocused])
+            .unwrap();
+
+        UnixStream::connect(events.socket_path())
+            .unwrap()
+            .write_all(b"window_created\n")
+            .unwrap();
+        UnixStream::connect(events.socket_path())
+            .unwrap()
+            .write_all(b"window_focused\n\0")
+            .unwrap();
+
+        assert_eq!(events.next().unwrap().event, Event::WindowFocused);
+    }
+
+    #[test]
+    fn signal_action_delivers_the_event_through_a_shell() {
+        let has_nc = Process::new("sh")
+            .args(["-c", "command -v nc"])
+            .output()
+            .is_ok_and(|output| output.status.success());
+
+        if !has_nc {
+            eprintln!("skipping, nc isn't installed");
+            return;
+        }
+
+        let mut events = YabaiClient::dry_run()
+            .subscribe(&[Event::WindowFocused])
+            .unwrap();
+
+        let mut action = Process::new("sh")
+            .arg("-c")
+            .arg(signal_action(Event::WindowFocused, events.socket_path()))
+            .env("YABAI_WINDOW_ID", "42")
+            .spawn()
+            .unwrap();
+
+        let event = events.next().unwrap();
+        assert_eq!(event.event, Event::WindowFocused);
+        assert_eq!(event.variable("YABAI_WINDOW_ID"), Some("42"));
+
+        assert!(action.wait().unwrap().success());
+    }
+}